@@ -42,78 +42,36 @@ pub fn build_contours(open_tile: OpenTile, nav_mesh_settings: &NavMeshSettings)
         contours: Vec::with_capacity(max_contours.into()),
     };
 
-    // Mark boundaries.
-    let mut boundry_flags = vec![0u8; open_tile.span_count];
-    for (cell_index, cell) in open_tile.cells.iter().enumerate() {
-        for span in cell.spans.iter() {
-            let mut res = 0;
-
-            for dir in 0..4 {
-                let mut other_region = 0;
-                if let Some(span_index) = span.neighbours[dir] {
-                    let other_span = &open_tile.cells
-                        [get_neighbour_index(nav_mesh_settings, cell_index, dir)]
-                    .spans[span_index as usize];
-                    other_region = other_span.region;
-                }
-
-                if span.region == other_region {
-                    res |= 1 << dir;
-                }
-            }
-
-            boundry_flags[span.tile_index] = res ^ 0b1111; // Flip so we mark unconnected sides.
-        }
-    }
-
-    let mut vertices = Vec::with_capacity(256);
     let mut simplified_vertices = Vec::with_capacity(64);
 
-    for (cell_index, cell) in open_tile.cells.iter().enumerate() {
-        for (span_index, span) in cell.spans.iter().enumerate() {
-            if boundry_flags[span.tile_index] == 0 || boundry_flags[span.tile_index] == 0b1111 {
-                boundry_flags[span.tile_index] = 0;
-                continue;
-            }
-            if span.region == 0 || span.area.is_none() {
-                continue;
-            }
-
-            vertices.clear();
-            simplified_vertices.clear();
-
-            // Walk contour
-            walk_contour(
-                cell_index,
-                span_index,
-                &open_tile,
-                nav_mesh_settings,
-                &mut boundry_flags,
-                &mut vertices,
-            );
+    walk_region_contours(&open_tile, nav_mesh_settings, |vertices, span| {
+        simplified_vertices.clear();
 
+        if nav_mesh_settings.raw_contours {
+            copy_raw_contour(vertices, &mut simplified_vertices);
+        } else {
             // Simplify contour
             simplify_contour(
-                &vertices,
+                vertices,
                 &mut simplified_vertices,
                 nav_mesh_settings.max_contour_simplification_error,
                 nav_mesh_settings.max_edge_length,
             );
+        }
 
-            // Remove degenerate segments.
-            remove_degenerate_segments(&mut simplified_vertices);
+        // Remove degenerate segments.
+        remove_degenerate_segments(&mut simplified_vertices);
 
-            if simplified_vertices.len() >= 3 {
-                let new_contour = Contour {
-                    vertices: simplified_vertices.clone(),
-                    region: span.region,
-                    area: span.area.unwrap(), // Already checked above.
-                };
+        if simplified_vertices.len() >= 3 {
+            let new_contour = Contour {
+                vertices: simplified_vertices.clone(),
+                region: span.region,
+                area: span.area.unwrap(), // Already checked in walk_region_contours.
+            };
 
-                contour_set.contours.push(new_contour);
-            }
+            contour_set.contours.push(new_contour);
         }
-    }
+    });
 
     // handle holes.
     if !contour_set.contours.is_empty() {
@@ -168,12 +126,74 @@ pub fn build_contours(open_tile: OpenTile, nav_mesh_settings: &NavMeshSettings)
     contour_set
 }
 
+/// Walks the outline of every region in ``open_tile``, calling ``on_contour`` with the raw (unsimplified) vertices & the span the walk started from.
+///
+/// Vertices are packed as 4 values each: x, y, z & flags.
+pub(super) fn walk_region_contours(
+    open_tile: &OpenTile,
+    nav_mesh_settings: &NavMeshSettings,
+    mut on_contour: impl FnMut(&[u32], &OpenSpan),
+) {
+    // Mark boundaries.
+    let mut boundry_flags = vec![0u8; open_tile.span_count];
+    for (cell_index, cell) in open_tile.cells.iter().enumerate() {
+        for span in cell.spans.iter() {
+            let mut res = 0;
+
+            for dir in 0..4 {
+                let mut other_region = 0;
+                if let Some(span_index) = span.neighbours[dir] {
+                    let other_span = &open_tile.cells
+                        [get_neighbour_index(nav_mesh_settings, cell_index, dir)]
+                    .spans[span_index as usize];
+                    other_region = other_span.region;
+                }
+
+                if span.region == other_region {
+                    res |= 1 << dir;
+                }
+            }
+
+            boundry_flags[span.tile_index] = res ^ 0b1111; // Flip so we mark unconnected sides.
+        }
+    }
+
+    let mut vertices = Vec::with_capacity(256);
+
+    for (cell_index, cell) in open_tile.cells.iter().enumerate() {
+        for (span_index, span) in cell.spans.iter().enumerate() {
+            if boundry_flags[span.tile_index] == 0 || boundry_flags[span.tile_index] == 0b1111 {
+                boundry_flags[span.tile_index] = 0;
+                continue;
+            }
+            if span.region == 0 || span.area.is_none() {
+                continue;
+            }
+
+            vertices.clear();
+
+            // Walk contour
+            walk_contour(
+                cell_index,
+                span_index,
+                open_tile,
+                nav_mesh_settings,
+                &mut boundry_flags,
+                &mut vertices,
+            );
+
+            on_contour(&vertices, span);
+        }
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 struct PotentialDiagonal {
     vertex: u32,
     distance: u32,
 }
 
+#[allow(clippy::unnecessary_sort_by)]
 fn merge_region_holes(region: &mut ContourRegion) {
     // Find left-most vertex
     for hole in region.holes.iter_mut() {
@@ -449,18 +469,14 @@ fn get_corner_height(
     dir: u8,
 ) -> u16 {
     let next_dir = (dir + 1) & 0x3;
-    let mut regions = [0; 4];
 
     let mut height = span.min;
 
-    regions[0] = span.region;
-
     if let Some(span_index) = span.neighbours[dir as usize] {
         let other_cell_index = get_neighbour_index(nav_mesh_settings, cell_index, dir.into());
         let other_span = &tile.cells[other_cell_index].spans[span_index as usize];
 
         height = height.max(other_span.min);
-        regions[1] = other_span.region;
 
         if let Some(span_index) = other_span.neighbours[next_dir as usize] {
             let other_cell_index =
@@ -468,7 +484,6 @@ fn get_corner_height(
             let other_span = &tile.cells[other_cell_index].spans[span_index as usize];
 
             height = height.max(other_span.min);
-            regions[2] = other_span.region;
         }
     }
 
@@ -477,7 +492,6 @@ fn get_corner_height(
         let other_span = &tile.cells[other_cell_index].spans[span_index as usize];
 
         height = height.max(other_span.min);
-        regions[3] = other_span.region;
 
         if let Some(span_index) = other_span.neighbours[dir as usize] {
             let other_cell_index =
@@ -485,19 +499,37 @@ fn get_corner_height(
             let other_span = &tile.cells[other_cell_index].spans[span_index as usize];
 
             height = height.max(other_span.min);
-            regions[2] = other_span.region;
         }
     }
 
     height
 }
 
+/// Copies every vertex of a raw contour, see [NavMeshSettings::raw_contours].
+fn copy_raw_contour(points: &[u32], contour: &mut Vec<UVec4>) {
+    let point_count = points.len() / 4;
+
+    contour.extend((0..point_count).map(|i| {
+        let next = (i + 1) % point_count;
+
+        UVec4::new(
+            points[i * 4],
+            points[i * 4 + 1],
+            points[i * 4 + 2],
+            (points[next * 4 + 3] & MASK_CONTOUR_REGION) | (points[i * 4 + 3] & FLAG_BORDER_VERTEX),
+        )
+    }));
+}
+
 fn simplify_contour(
     points: &[u32],
     simplified: &mut Vec<UVec4>,
     max_error: f32,
     max_edge_len: u32,
 ) {
+    // The error is compared squared so a negative error would behave like a positive one, NaN would never add any points.
+    let max_error = max_error.max(0.0);
+
     let has_connections = {
         let mut has_connections = false;
 
@@ -4,9 +4,12 @@ use bevy_rapier3d::{rapier::prelude::{Ball, Cuboid, Capsule, Cylinder, Triangle,
 use crate::heightfields::TriangleCollection;
 
 pub(super) struct GeometryCollection {
+    /// Transform of the collider including its scale, the geometry itself is unscaled.
     pub(super) transform: Transform,
     pub(super) geometry_to_convert: GeometryToConvert,
     pub(super) area: Option<u16>,
+    pub(super) max_traversable_slope_radians: f32,
+    pub(super) step_height: u16,
 }
 
 pub(super) enum ColliderType {
@@ -37,7 +40,9 @@ pub(super) fn convert_geometry_collections(
         TriangleCollection {
             transform: geometry_collection.transform,
             triangles: convert_geometry(geometry_collection.geometry_to_convert),
-            area: geometry_collection.area
+            area: geometry_collection.area,
+            max_traversable_slope_radians: geometry_collection.max_traversable_slope_radians,
+            step_height: geometry_collection.step_height,
         }
     }).collect()
 }
@@ -0,0 +1,316 @@
+//! Visualization of the intermediate stages of nav-mesh generation. Requires the ``debug_draw`` feature.
+//!
+//! Tiles in [NavMeshDebugStage::tiles] keep their intermediate results as [TileDebugArtifacts] the next time they're generated, which are drawn using [bevy_prototype_debug_lines].
+//! Add [DebugLinesPlugin](bevy_prototype_debug_lines::DebugLinesPlugin) to your app for anything to be drawn.
+//!
+//! This is meant for diagnosing generation issues & not for release builds.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    prelude::{Color, DetectChanges, Quat, Res, ResMut, Resource, UVec2, Vec3},
+    utils::HashMap,
+};
+use bevy_prototype_debug_lines::DebugLines;
+
+use crate::{
+    contour::walk_region_contours,
+    heightfields::{OpenTile, VoxelizedTile},
+    DirtyTiles, NavMeshSettings, TileAffectors,
+};
+
+/// Stage of nav-mesh generation to draw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugStage {
+    /// Solid spans from voxelizing the geometry. Green if traversable, red if not.
+    VoxelSpans,
+    /// Distance of each walkable span to the closest edge, from blue (at the edge) to red (furthest away).
+    DistanceField,
+    /// Region of each walkable span, each region getting its own color.
+    Regions,
+    /// Outline of each region before being simplified.
+    RawContours,
+}
+
+/// Chooses which stage of nav-mesh generation is drawn & for which tiles.
+///
+/// Changing the selected tiles regenerates them so that their [TileDebugArtifacts] are captured.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct NavMeshDebugStage {
+    /// Stage to draw. ``None`` disables drawing, the selected tiles' artifacts are still captured.
+    pub stage: Option<DebugStage>,
+    /// Tiles to capture & draw the intermediate results of.
+    pub tiles: Vec<UVec2>,
+}
+
+/// Intermediate results of generating a single tile. All positions are in nav-mesh space, use [NavMeshSettings::to_world_space] to convert them.
+#[derive(Default, Clone, Debug)]
+pub struct TileDebugArtifacts {
+    /// Solid spans from voxelization as their bottom & top in the center of the cell and if they are traversable.
+    pub voxel_spans: Vec<(Vec3, Vec3, bool)>,
+    /// Floor of each walkable span after eroding the walkable area.
+    pub open_spans: Vec<Vec3>,
+    /// Distance field value of each span in [TileDebugArtifacts::open_spans].
+    pub distances: Vec<u16>,
+    /// Largest value in [TileDebugArtifacts::distances].
+    pub max_distance: u16,
+    /// Region of each span in [TileDebugArtifacts::open_spans]. ``0`` means the span isn't part of a region.
+    pub regions: Vec<u16>,
+    /// Outline of each region before simplification along with the region.
+    pub raw_contours: Vec<(u16, Vec<Vec3>)>,
+}
+
+impl TileDebugArtifacts {
+    pub(crate) fn capture_voxelized_tile(
+        &mut self,
+        tile_coord: UVec2,
+        voxelized_tile: &VoxelizedTile,
+        nav_mesh_settings: &NavMeshSettings,
+    ) {
+        self.voxel_spans = voxelized_tile
+            .spans()
+            .map(|(cell_index, min, max, traversable)| {
+                let position = get_cell_position(tile_coord, cell_index, nav_mesh_settings);
+
+                (
+                    get_span_position(position, min, nav_mesh_settings),
+                    get_span_position(position, max, nav_mesh_settings),
+                    traversable,
+                )
+            })
+            .collect();
+    }
+
+    pub(crate) fn capture_distance_field(
+        &mut self,
+        tile_coord: UVec2,
+        open_tile: &OpenTile,
+        nav_mesh_settings: &NavMeshSettings,
+    ) {
+        self.open_spans = vec![Vec3::ZERO; open_tile.span_count];
+        for (cell_index, cell) in open_tile.cells.iter().enumerate() {
+            let position = get_cell_position(tile_coord, cell_index, nav_mesh_settings);
+
+            for span in cell.spans.iter() {
+                self.open_spans[span.tile_index] =
+                    get_span_position(position, span.min, nav_mesh_settings);
+            }
+        }
+
+        self.distances = open_tile.distances.clone();
+        self.max_distance = open_tile.max_distance;
+    }
+
+    pub(crate) fn capture_regions(&mut self, open_tile: &OpenTile) {
+        self.regions = vec![0; open_tile.span_count];
+        for span in open_tile.cells.iter().flat_map(|cell| cell.spans.iter()) {
+            self.regions[span.tile_index] = span.region;
+        }
+    }
+
+    pub(crate) fn capture_raw_contours(
+        &mut self,
+        tile_coord: UVec2,
+        open_tile: &OpenTile,
+        nav_mesh_settings: &NavMeshSettings,
+    ) {
+        let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+
+        self.raw_contours.clear();
+        walk_region_contours(open_tile, nav_mesh_settings, |vertices, span| {
+            let vertices = vertices
+                .chunks_exact(4)
+                .map(|vertex| {
+                    Vec3::new(
+                        tile_origin.x + vertex[0] as f32 * nav_mesh_settings.cell_width,
+                        nav_mesh_settings.world_bottom_bound
+                            + vertex[1] as f32 * nav_mesh_settings.cell_height,
+                        tile_origin.y + vertex[2] as f32 * nav_mesh_settings.cell_width,
+                    )
+                })
+                .collect();
+
+            self.raw_contours.push((span.region, vertices));
+        });
+    }
+}
+
+/// The [TileDebugArtifacts] of tiles selected in [NavMeshDebugStage::tiles], updated as the tiles are generated.
+#[derive(Resource, Default, Clone)]
+pub struct NavMeshDebugArtifacts(Arc<Mutex<HashMap<UVec2, TileDebugArtifacts>>>);
+
+impl NavMeshDebugArtifacts {
+    /// Returns the captured artifacts of a tile if any.
+    pub fn get(&self, tile: UVec2) -> Option<TileDebugArtifacts> {
+        self.0.lock().ok()?.get(&tile).cloned()
+    }
+
+    pub(crate) fn insert(&self, tile: UVec2, artifacts: TileDebugArtifacts) {
+        if let Ok(mut tiles) = self.0.lock() {
+            tiles.insert(tile, artifacts);
+        }
+    }
+}
+
+/// Regenerates newly selected tiles so their artifacts are captured & drops the artifacts of tiles no longer selected.
+pub(crate) fn update_debug_tiles_system(
+    debug_stage: Res<NavMeshDebugStage>,
+    debug_artifacts: Res<NavMeshDebugArtifacts>,
+    nav_mesh_settings: Res<NavMeshSettings>,
+    tile_affectors: Res<TileAffectors>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+) {
+    if !debug_stage.is_changed() {
+        return;
+    }
+
+    let Ok(mut artifacts) = debug_artifacts.0.lock() else {
+        return;
+    };
+    artifacts.retain(|tile, _| debug_stage.tiles.contains(tile));
+
+    for tile in debug_stage.tiles.iter() {
+        if !artifacts.contains_key(tile) && tile_affectors.contains_key(tile) {
+            dirty_tiles.mark_tile(*tile, &nav_mesh_settings);
+        }
+    }
+}
+
+pub(crate) fn draw_nav_mesh_debug_stage_system(
+    debug_stage: Res<NavMeshDebugStage>,
+    debug_artifacts: Res<NavMeshDebugArtifacts>,
+    nav_mesh_settings: Res<NavMeshSettings>,
+    lines: Option<ResMut<DebugLines>>,
+) {
+    let (Some(stage), Some(mut lines)) = (debug_stage.stage, lines) else {
+        return;
+    };
+    let Ok(artifacts) = debug_artifacts.0.lock() else {
+        return;
+    };
+
+    let half_cell = nav_mesh_settings.cell_width * 0.5;
+    // Artifacts are captured in nav-mesh space.
+    let to_world = nav_mesh_settings.get_up_rotation().inverse();
+    for tile_artifacts in debug_stage
+        .tiles
+        .iter()
+        .filter_map(|tile| artifacts.get(tile))
+    {
+        match stage {
+            DebugStage::VoxelSpans => {
+                for (bottom, top, traversable) in tile_artifacts.voxel_spans.iter() {
+                    let color = if *traversable {
+                        Color::GREEN
+                    } else {
+                        Color::RED
+                    };
+
+                    lines.line_colored(to_world * *bottom, to_world * *top, 0.0, color);
+                }
+            }
+            DebugStage::DistanceField => {
+                let max_distance = f32::from(tile_artifacts.max_distance.max(1));
+
+                for (position, distance) in tile_artifacts
+                    .open_spans
+                    .iter()
+                    .zip(tile_artifacts.distances.iter())
+                {
+                    let t = f32::from(*distance) / max_distance;
+
+                    draw_cross(
+                        &mut lines,
+                        to_world,
+                        *position,
+                        half_cell,
+                        Color::rgb(t, 0.0, 1.0 - t),
+                    );
+                }
+            }
+            DebugStage::Regions => {
+                for (position, region) in tile_artifacts
+                    .open_spans
+                    .iter()
+                    .zip(tile_artifacts.regions.iter())
+                {
+                    draw_cross(
+                        &mut lines,
+                        to_world,
+                        *position,
+                        half_cell,
+                        get_region_color(*region),
+                    );
+                }
+            }
+            DebugStage::RawContours => {
+                for (region, vertices) in tile_artifacts.raw_contours.iter() {
+                    let color = get_region_color(*region);
+
+                    for (i, vertex) in vertices.iter().enumerate() {
+                        let next = vertices[(i + 1) % vertices.len()];
+
+                        lines.line_colored(to_world * *vertex, to_world * next, 0.0, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the center of a cell on the XZ-plane at the bottom of the world.
+fn get_cell_position(
+    tile_coord: UVec2,
+    cell_index: usize,
+    nav_mesh_settings: &NavMeshSettings,
+) -> Vec3 {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+
+    let column = (cell_index % tile_side) as f32 + 0.5;
+    let row = (cell_index / tile_side) as f32 + 0.5;
+
+    Vec3::new(
+        tile_origin.x + column * nav_mesh_settings.cell_width,
+        nav_mesh_settings.world_bottom_bound,
+        tile_origin.y + row * nav_mesh_settings.cell_width,
+    )
+}
+
+fn get_span_position(
+    cell_position: Vec3,
+    height: u16,
+    nav_mesh_settings: &NavMeshSettings,
+) -> Vec3 {
+    cell_position + Vec3::Y * (f32::from(height) * nav_mesh_settings.cell_height)
+}
+
+fn get_region_color(region: u16) -> Color {
+    if region == 0 {
+        return Color::BLACK;
+    }
+
+    // Golden angle so neighbouring region ids get distinct hues.
+    Color::hsl((f32::from(region) * 137.5) % 360.0, 0.8, 0.5)
+}
+
+fn draw_cross(
+    lines: &mut DebugLines,
+    to_world: Quat,
+    position: Vec3,
+    half_size: f32,
+    color: Color,
+) {
+    lines.line_colored(
+        to_world * (position - Vec3::X * half_size),
+        to_world * (position + Vec3::X * half_size),
+        0.0,
+        color,
+    );
+    lines.line_colored(
+        to_world * (position - Vec3::Z * half_size),
+        to_world * (position + Vec3::Z * half_size),
+        0.0,
+        color,
+    );
+}
@@ -1,18 +1,24 @@
-use std::{cmp::Ordering, ops::Div, sync::Arc};
+use std::{ops::Div, sync::Arc};
 
-use bevy::prelude::{IVec3, UVec2, Vec3, Transform};
+use bevy::{math::DVec3, prelude::{IVec2, IVec3, UVec2, Vec2, Vec3, Transform}, log::warn};
 use bevy_rapier3d::rapier::prelude::HeightField;
 
 use crate::conversion::Triangles;
 
 use super::{get_neighbour_index, NavMeshSettings};
 
+/// Overlapping spans from different affectors in a cell are merged into one surface if their tops are at most this many voxels of ``cell_height`` apart.
+const SPAN_MERGE_THRESHOLD: u16 = 1;
+
 #[derive(Default, Clone, Debug)]
 struct HeightSpan {
     min: u16,
     max: u16,
     traversable: bool,
     area: Option<u16>,
+    step_height: u16,
+    /// Index of the affector the top of this span belongs to, only used to tell affectors apart when merging spans.
+    affector: usize,
 }
 
 #[derive(Default, Clone)]
@@ -25,6 +31,25 @@ pub struct VoxelizedTile {
     cells: Vec<VoxelCell>, // len = tiles_along_width^2. Laid out X to Y
 }
 
+#[cfg(feature = "debug_draw")]
+impl VoxelizedTile {
+    /// Returns every solid span as its cell index, bottom, top & if it's traversable.
+    pub(super) fn spans(&self) -> impl Iterator<Item = (usize, u16, u16, bool)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(i, cell)| {
+            cell.spans
+                .iter()
+                .map(move |span| (i, span.min, span.max, span.traversable))
+        })
+    }
+}
+
+/// Inclusive range of cells within a tile (including the border) on the XZ-plane. Empty if ``min`` is greater than ``max`` on either axis.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct CellRange {
+    pub(super) min: IVec2,
+    pub(super) max: IVec2,
+}
+
 #[derive(Default, Clone, Debug)]
 pub(super) struct OpenCell {
     pub(super) spans: Vec<OpenSpan>,
@@ -39,6 +64,7 @@ pub(super) struct OpenSpan {
     pub(super) tile_index: usize, // The index of this span in the whole tile.
     pub(super) region: u16, // Region if non-zero.
     pub(super) area: Option<u16>,
+    pub(super) step_height: u16,
 }
 
 #[derive(Default, Debug)]
@@ -50,55 +76,327 @@ pub struct OpenTile {
     pub(super) span_count: usize, // Total spans in all cells.
     pub(super) max_regions: u16,
 }
+impl OpenTile {
+    /// Returns true if any span inside the tile itself (ignoring the border) matches ``predicate``.
+    pub(super) fn any_span_in_tile(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        predicate: impl Fn(&OpenSpan) -> bool,
+    ) -> bool {
+        let tile_side = nav_mesh_settings.get_tile_side_with_border();
+        let border_side = nav_mesh_settings.get_border_side();
+
+        (border_side..tile_side - border_side).any(|z| {
+            (border_side..tile_side - border_side)
+                .any(|x| self.cells[x + z * tile_side].spans.iter().any(&predicate))
+        })
+    }
+}
+/// A span in a [ClearanceField].
+struct ClearanceSpan {
+    /// Same as [OpenSpan::min].
+    floor: u16,
+    /// In the same units as [OpenTile::distances].
+    distance: u16,
+    step_height: u16,
+}
+
+/// Distance from every span of a tile to the closest wall along with the spans' floors & step heights, see [calculate_clearance_field].
+///
+/// Positions are in cells of the tile including its border, like [PolyMesh::vertices](crate::mesher::PolyMesh::vertices).
+pub(super) struct ClearanceField {
+    /// Index of every cell's first span in ``spans``, followed by the total span count.
+    cell_starts: Vec<usize>,
+    spans: Vec<ClearanceSpan>,
+}
+impl ClearanceField {
+    /// Returns the spans in the four cells around ``position`` at most their step height above or below it.
+    fn get_spans_near<'a>(&'a self, position: Vec3, nav_mesh_settings: &'a NavMeshSettings) -> impl Iterator<Item = &'a ClearanceSpan> {
+        let tile_side = nav_mesh_settings.get_tile_side_with_border() as i32;
+        let min = (Vec2::new(position.x, position.z) - 0.5).floor().as_ivec2();
+
+        (min.y..=min.y + 1)
+            .flat_map(move |z| (min.x..=min.x + 1).map(move |x| (x, z)))
+            .filter(move |(x, z)| *x >= 0 && *z >= 0 && *x < tile_side && *z < tile_side)
+            .flat_map(move |(x, z)| {
+                let cell = (x + z * tile_side) as usize;
+                &self.spans[self.cell_starts[cell]..self.cell_starts[cell + 1]]
+            })
+            .filter(move |span| (f32::from(span.floor) - position.y).abs() <= f32::from(span.step_height))
+    }
+
+    /// Returns the distance on the XZ-plane from ``position`` to the closest wall in world units, minus ``walkable_radius`` so it's the distance to the edge of the nav-mesh. [f32::INFINITY] if no wall was found.
+    ///
+    /// The span in the four cells around ``position`` whose floor is closest to it is used, the one closest to a wall if several are equally close.
+    pub(super) fn get_clearance(&self, position: Vec3, nav_mesh_settings: &NavMeshSettings) -> f32 {
+        let closest_span = self
+            .get_spans_near(position, nav_mesh_settings)
+            .min_by(|a, b| {
+                let a_height = (f32::from(a.floor) - position.y).abs();
+                let b_height = (f32::from(b.floor) - position.y).abs();
+
+                a_height.total_cmp(&b_height).then(a.distance.cmp(&b.distance))
+            });
+
+        match closest_span.map(|span| span.distance) {
+            None => 0.0,
+            Some(distance) if distance >= NO_WALL_DISTANCE => f32::INFINITY,
+            // Distances are in half cells.
+            Some(distance) => ((f32::from(distance) / 2.0 - f32::from(nav_mesh_settings.walkable_radius)) * nav_mesh_settings.cell_width).max(0.0),
+        }
+    }
+
+    /// Returns the largest step height of the spans along the segment from ``a`` to ``b``, [NavMeshSettings::step_height] if there are none.
+    pub(super) fn get_step_height(&self, a: Vec3, b: Vec3, nav_mesh_settings: &NavMeshSettings) -> u16 {
+        // One sample per cell along the segment.
+        let samples = Vec2::new(b.x - a.x, b.z - a.z).abs().max_element().ceil().max(1.0) as usize;
+
+        (0..=samples)
+            .flat_map(|i| self.get_spans_near(a.lerp(b, i as f32 / samples as f32), nav_mesh_settings))
+            .map(|span| span.step_height)
+            .max()
+            .unwrap_or(nav_mesh_settings.step_height)
+    }
+
+    /// Returns the largest difference in floor height between the spans on either side of the segment from ``a`` to ``b``, ``0`` if there are none.
+    ///
+    /// Polygons within a tile share their vertices so this is the only way to tell how large a step crossing their shared edge is.
+    pub(super) fn get_vertical_delta(
+        &self,
+        a: Vec3,
+        b: Vec3,
+        nav_mesh_settings: &NavMeshSettings,
+    ) -> u16 {
+        let direction = Vec2::new(b.x - a.x, b.z - a.z);
+        // One sample per cell along the segment, comparing the cells half a cell to either side of it.
+        let samples = direction.abs().max_element().ceil().max(1.0) as usize;
+        let offset = direction.normalize_or_zero().perp() * 0.5;
+        let offset = Vec3::new(offset.x, 0.0, offset.y);
+
+        (0..samples)
+            .filter_map(|i| {
+                let position = a.lerp(b, (i as f32 + 0.5) / samples as f32);
+                let left = self.get_floor_in_cell(position + offset, nav_mesh_settings)?;
+                let right = self.get_floor_in_cell(position - offset, nav_mesh_settings)?;
+
+                Some(left.abs_diff(right))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the floor of the span in the cell containing ``position`` closest to it, ignoring spans further away than their step height.
+    fn get_floor_in_cell(
+        &self,
+        position: Vec3,
+        nav_mesh_settings: &NavMeshSettings,
+    ) -> Option<u16> {
+        let tile_side = nav_mesh_settings.get_tile_side_with_border() as i32;
+        let cell = Vec2::new(position.x, position.z).floor().as_ivec2();
+        if cell.x < 0 || cell.y < 0 || cell.x >= tile_side || cell.y >= tile_side {
+            return None;
+        }
+
+        let cell = (cell.x + cell.y * tile_side) as usize;
+        self.spans[self.cell_starts[cell]..self.cell_starts[cell + 1]]
+            .iter()
+            .map(|span| (span, (f32::from(span.floor) - position.y).abs()))
+            .filter(|(span, distance)| *distance <= f32::from(span.step_height))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(span, _)| span.floor)
+    }
+}
+
 pub(super) struct TriangleCollection {
+    /// Transform of the collider including its scale, the triangles themselves are unscaled.
     pub(super) transform: Transform,
     pub(super) triangles: Triangles,
     pub(super) area: Option<u16>,
+    /// [NavMeshSettings::max_traversable_slope_radians] with any [NavMeshAffectorOverrides](crate::NavMeshAffectorOverrides) applied.
+    pub(super) max_traversable_slope_radians: f32,
+    /// [NavMeshSettings::step_height] with any [NavMeshAffectorOverrides](crate::NavMeshAffectorOverrides) applied.
+    pub(super) step_height: u16,
 }
 
 pub(super) struct HeightFieldCollection {
+    /// Transform of the collider including its scale, the heightfield itself is unscaled.
     pub(super) transform: Transform,
     pub(super) heightfield: Arc<HeightField>,
     pub(super) area: Option<u16>,
+    /// [NavMeshSettings::max_traversable_slope_radians] with any [NavMeshAffectorOverrides](crate::NavMeshAffectorOverrides) applied.
+    pub(super) max_traversable_slope_radians: f32,
+    /// [NavMeshSettings::step_height] with any [NavMeshAffectorOverrides](crate::NavMeshAffectorOverrides) applied.
+    pub(super) step_height: u16,
 }
 
+/// Voxelizes the geometry within a tile.
+///
+/// If ``previous`` is supplied only the cells within its [CellRange] are re-rasterized, every other cell is kept from the previous [VoxelizedTile].
+/// This is only correct if no geometry outside of the range changed since the previous tile was built.
 pub(super) fn build_heightfield_tile(
     tile_coord: UVec2,
     triangle_collections: Vec<TriangleCollection>,
     heightfields: Vec<HeightFieldCollection>,
     nav_mesh_settings: &NavMeshSettings,
+    previous: Option<(VoxelizedTile, CellRange)>,
 ) -> VoxelizedTile {
     let tile_side = nav_mesh_settings.get_tile_side_with_border();
-    let mut voxel_tile = VoxelizedTile {
-        cells: vec![VoxelCell::default(); tile_side.pow(2)],
+
+    let (mut voxel_tile, clip_min, clip_max) = match previous {
+        Some((mut voxel_tile, dirty_cells)) if voxel_tile.cells.len() == tile_side.pow(2) => {
+            let clip_min = dirty_cells.min.max(IVec2::ZERO);
+            let clip_max = dirty_cells.max.min(IVec2::splat((tile_side - 1) as i32));
+
+            for z in clip_min.y..=clip_max.y {
+                for x in clip_min.x..=clip_max.x {
+                    voxel_tile.cells[x as usize + z as usize * tile_side].spans.clear();
+                }
+            }
+
+            (voxel_tile, IVec3::new(clip_min.x, 0, clip_min.y), IVec3::new(clip_max.x, 0, clip_max.y))
+        }
+        _ => {
+            let voxel_tile = VoxelizedTile {
+                cells: vec![VoxelCell::default(); tile_side.pow(2)],
+            };
+
+            (voxel_tile, IVec3::ZERO, IVec3::new((tile_side - 1) as i32, 0, (tile_side - 1) as i32))
+        }
     };
 
-    let tile_max_bound = IVec3::new((tile_side - 1) as i32, 0, (tile_side - 1) as i32);
+    if clip_min.x > clip_max.x || clip_min.z > clip_max.z {
+        // Nothing to re-rasterize.
+        return voxel_tile;
+    }
+
+    let cell_offset = get_first_cell(tile_coord, nav_mesh_settings);
+    let clamped_above = rasterize_geometry(&triangle_collections, &heightfields, cell_offset, nav_mesh_settings, clip_min, clip_max, tile_side, &mut voxel_tile);
+
+    if clamped_above {
+        warn!(
+            "Geometry in tile {} is above the maximum representable height of {} and has been clamped. Increase cell_height or raise world_bottom_bound to fit it.",
+            tile_coord,
+            nav_mesh_settings.max_representable_height()
+        );
+    }
+
+    voxel_tile
+}
+
+/// Voxelizes the geometry within a block of ``block_size`` tiles starting at ``block_min`` at once, returning a [VoxelizedTile] for each tile.
+///
+/// Tiles are returned row by row, X first. Each tile is the same as if it was voxelized on its own with [build_heightfield_tile] as both rasterize relative to the same cells, but geometry in the borders shared between the tiles is only rasterized once.
+pub(super) fn build_heightfield_block(
+    block_min: UVec2,
+    block_size: UVec2,
+    triangle_collections: Vec<TriangleCollection>,
+    heightfields: Vec<HeightFieldCollection>,
+    nav_mesh_settings: &NavMeshSettings,
+) -> Vec<VoxelizedTile> {
+    let tile_width = usize::from(nav_mesh_settings.tile_width);
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let border_side = nav_mesh_settings.get_border_side();
+
+    let block_width = block_size.x as usize * tile_width + border_side * 2;
+    let block_depth = block_size.y as usize * tile_width + border_side * 2;
+
+    let mut voxel_block = VoxelizedTile {
+        cells: vec![VoxelCell::default(); block_width * block_depth],
+    };
+
+    let cell_offset = get_first_cell(block_min, nav_mesh_settings);
+    let clip_max = IVec3::new(block_width as i32 - 1, 0, block_depth as i32 - 1);
+    let clamped_above = rasterize_geometry(&triangle_collections, &heightfields, cell_offset, nav_mesh_settings, IVec3::ZERO, clip_max, block_width, &mut voxel_block);
+
+    if clamped_above {
+        warn!(
+            "Geometry in tiles {} to {} is above the maximum representable height of {} and has been clamped. Increase cell_height or raise world_bottom_bound to fit it.",
+            block_min,
+            block_min + block_size - UVec2::ONE,
+            nav_mesh_settings.max_representable_height()
+        );
+    }
+
+    // Split the block into tiles, cells in the borders are copied into every tile they are part of.
+    let mut voxel_tiles = Vec::with_capacity((block_size.x * block_size.y) as usize);
+    for tile_z in 0..block_size.y as usize {
+        for tile_x in 0..block_size.x as usize {
+            let cells = (0..tile_side)
+                .flat_map(|z| {
+                    let row_start = tile_x * tile_width + (tile_z * tile_width + z) * block_width;
+
+                    voxel_block.cells[row_start..row_start + tile_side].iter().cloned()
+                })
+                .collect();
+
+            voxel_tiles.push(VoxelizedTile { cells });
+        }
+    }
+
+    voxel_tiles
+}
+
+/// Returns the index of the first cell of ``tile_coord``, including the border, counted from the first cell of tile ``(0, 0)``.
+fn get_first_cell(tile_coord: UVec2, nav_mesh_settings: &NavMeshSettings) -> IVec3 {
+    let first_cell = tile_coord * u32::from(nav_mesh_settings.tile_width);
+
+    IVec3::new(first_cell.x as i32, 0, first_cell.y as i32)
+}
+
+/// Rasterizes all triangles of ``triangle_collections`` & ``heightfields`` into the cells of ``voxel_tile`` between ``clip_min`` & ``clip_max``. ``cell_offset`` is the index of ``voxel_tile``'s first cell, see [get_first_cell].
+///
+/// Triangles are rasterized relative to the first cell of tile ``(0, 0)`` no matter which cells are rasterized, this way a cell always gets the same spans whether it's voxelized on its own, as part of a tile or as part of a block of tiles.
+/// Rasterizing is done in double precision so cells far from tile ``(0, 0)`` don't lose precision compared to rasterizing relative to their own tile.
+///
+/// Returns true if any geometry was clamped for being above [NavMeshSettings::max_representable_height].
+#[allow(clippy::too_many_arguments)]
+fn rasterize_geometry(
+    triangle_collections: &[TriangleCollection],
+    heightfields: &[HeightFieldCollection],
+    cell_offset: IVec3,
+    nav_mesh_settings: &NavMeshSettings,
+    clip_min: IVec3,
+    clip_max: IVec3,
+    row_width: usize,
+    voxel_tile: &mut VoxelizedTile,
+) -> bool {
+    let origin = nav_mesh_settings.get_tile_origin_with_border(UVec2::ZERO);
+    let origin = Vec3::new(origin.x, nav_mesh_settings.world_bottom_bound, origin.y).as_dvec3();
+    let (clip_min, clip_max) = (clip_min + cell_offset, clip_max + cell_offset);
 
-    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
-    let tile_origin = Vec3::new(
-        tile_origin.x,
-        nav_mesh_settings.world_bottom_bound,
-        tile_origin.y,
-    );
-    
     let mut translated_vertices = Vec::with_capacity(3);
+    let mut clamped_above = false;
 
-    for collection in triangle_collections.iter() {
-        let transform = collection.transform.with_scale(Vec3::ONE); // The collider returned from rapier already has scale applied to it, so we reset it here.
+    for (affector, collection) in triangle_collections.iter().enumerate() {
+        let transform = collection.transform;
 
         match &collection.triangles {
             Triangles::Triangle(vertices) => {
-                let translated_vertices = vertices.map(|vertex| transform.transform_point(vertex) - tile_origin);
-
-                process_triangle(translated_vertices[0], translated_vertices[1], translated_vertices[2], nav_mesh_settings, tile_max_bound, tile_side, &mut voxel_tile, collection.area);
+                let translated_vertices = vertices.map(|vertex| transform.transform_point(vertex).as_dvec3() - origin);
+
+                process_triangle(
+                    translated_vertices[0],
+                    translated_vertices[1],
+                    translated_vertices[2],
+                    nav_mesh_settings,
+                    clip_min,
+                    clip_max,
+                    cell_offset,
+                    row_width,
+                    voxel_tile,
+                    affector,
+                    collection.area,
+                    collection.max_traversable_slope_radians,
+                    collection.step_height,
+                    &mut clamped_above,
+                );
             },
             Triangles::TriMesh(vertices, triangles) => {
                 translated_vertices.clear();
                 translated_vertices.extend(
                     vertices
                         .iter()
-                        .map(|vertex| transform.transform_point(*vertex) - tile_origin),
+                        .map(|vertex| transform.transform_point(*vertex).as_dvec3() - origin),
                 ); // Transform vertices.
         
                 for triangle in triangles.iter() {
@@ -106,44 +404,95 @@ pub(super) fn build_heightfield_tile(
                     let b = translated_vertices[triangle[1] as usize];
                     let c = translated_vertices[triangle[2] as usize];
         
-                    process_triangle(a, b, c, nav_mesh_settings, tile_max_bound, tile_side, &mut voxel_tile, collection.area);
+                    process_triangle(
+                        a,
+                        b,
+                        c,
+                        nav_mesh_settings,
+                        clip_min,
+                        clip_max,
+                        cell_offset,
+                        row_width,
+                        voxel_tile,
+                        affector,
+                        collection.area,
+                        collection.max_traversable_slope_radians,
+                        collection.step_height,
+                        &mut clamped_above,
+                    );
                 }
             },
         }
     }
 
-    for collection in heightfields.iter() {
-        let transform = collection.transform.with_scale(Vec3::ONE); // The collider returned from rapier already has scale applied to it, so we reset it here.
+    for (affector, collection) in heightfields.iter().enumerate() {
+        let affector = triangle_collections.len() + affector;
+        let transform = collection.transform;
 
         for triangle in collection.heightfield.triangles() {
-            let a = transform.transform_point(Vec3::new(triangle.a.x, triangle.a.y, triangle.a.z)) - tile_origin;
-            let b = transform.transform_point(Vec3::new(triangle.b.x, triangle.b.y, triangle.b.z)) - tile_origin;
-            let c = transform.transform_point(Vec3::new(triangle.c.x, triangle.c.y, triangle.c.z)) - tile_origin;
-
-            process_triangle(a, b, c, nav_mesh_settings, tile_max_bound, tile_side, &mut voxel_tile, collection.area);
+            let a = transform.transform_point(Vec3::new(triangle.a.x, triangle.a.y, triangle.a.z)).as_dvec3() - origin;
+            let b = transform.transform_point(Vec3::new(triangle.b.x, triangle.b.y, triangle.b.z)).as_dvec3() - origin;
+            let c = transform.transform_point(Vec3::new(triangle.c.x, triangle.c.y, triangle.c.z)).as_dvec3() - origin;
+
+            process_triangle(
+                a,
+                b,
+                c,
+                nav_mesh_settings,
+                clip_min,
+                clip_max,
+                cell_offset,
+                row_width,
+                voxel_tile,
+                affector,
+                collection.area,
+                collection.max_traversable_slope_radians,
+                collection.step_height,
+                &mut clamped_above,
+            );
         }
     }
 
-    voxel_tile
+    clamped_above
 }
 
-fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettings, tile_max_bound: IVec3, tile_side: usize, voxel_tile: &mut VoxelizedTile, area: Option<u16>) {
-    let min_bound = a.min(b).min(c).div(nav_mesh_settings.cell_width).as_ivec3();
-    let max_bound = a.max(b).max(c).div(nav_mesh_settings.cell_width).as_ivec3();
-
-    // Check if triangle is completely outside the tile.
-    if max_bound.x < 0
-        || max_bound.z < 0
-        || min_bound.x > tile_max_bound.x
-        || min_bound.z > tile_max_bound.z
+/// Rasterizes a triangle into ``voxel_tile``, see [rasterize_geometry]. Sets ``clamped_above`` if any part of the triangle was above [NavMeshSettings::max_representable_height].
+#[allow(clippy::too_many_arguments)]
+fn process_triangle(
+    a: DVec3,
+    b: DVec3,
+    c: DVec3,
+    nav_mesh_settings: &NavMeshSettings,
+    clip_min: IVec3,
+    clip_max: IVec3,
+    cell_offset: IVec3,
+    row_width: usize,
+    voxel_tile: &mut VoxelizedTile,
+    affector: usize,
+    area: Option<u16>,
+    max_traversable_slope_radians: f32,
+    step_height: u16,
+    clamped_above: &mut bool,
+) {
+    let cell_width = f64::from(nav_mesh_settings.cell_width);
+    let cell_height = f64::from(nav_mesh_settings.cell_height);
+
+    let min_bound = a.min(b).min(c).div(cell_width).as_ivec3();
+    let max_bound = a.max(b).max(c).div(cell_width).as_ivec3();
+
+    // Check if triangle is completely outside the cells we are rasterizing.
+    if max_bound.x < clip_min.x
+        || max_bound.z < clip_min.z
+        || min_bound.x > clip_max.x
+        || min_bound.z > clip_max.z
     {
         return;
     }
 
-    let clamped_bound_min = min_bound.max(IVec3::ZERO);
-    let clamped_bound_max = max_bound.min(tile_max_bound);
-    let traversable = is_triangle_traversable(&a, &b, &c, nav_mesh_settings);
-    let vertices = [a, b, c, Vec3::ZERO, Vec3::ZERO, Vec3::ZERO, Vec3::ZERO];
+    let clamped_bound_min = min_bound.max(clip_min);
+    let clamped_bound_max = max_bound.min(clip_max);
+    let traversable = is_triangle_traversable(&a, &b, &c, max_traversable_slope_radians);
+    let vertices = [a, b, c, DVec3::ZERO, DVec3::ZERO, DVec3::ZERO, DVec3::ZERO];
 
     // For cache reasons we go.
     // --> X
@@ -153,8 +502,8 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
     // X is column. Z is row.
     // Which means we iterate Z first.
     for z in clamped_bound_min.z..=clamped_bound_max.z {
-        let row_clip_min = z as f32 * nav_mesh_settings.cell_width;
-        let row_clip_max = row_clip_min + nav_mesh_settings.cell_width;
+        let row_clip_min = f64::from(z) * cell_width;
+        let row_clip_max = row_clip_min + cell_width;
 
         // Clip polygon to the row.
         let (_, _, row_min_clip_vert_count, row_min_clip_verts) =
@@ -176,13 +525,12 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
             column_min_vert_x = column_min_vert_x.min(vertex.x);
             column_max_vert_x = column_max_vert_x.max(vertex.x);
         }
-        let column_min = ((column_min_vert_x / nav_mesh_settings.cell_width) as i32).max(0);
-        let column_max = ((column_max_vert_x / nav_mesh_settings.cell_width) as i32)
-            .min((tile_side - 1) as i32);
+        let column_min = ((column_min_vert_x / cell_width) as i32).max(clip_min.x);
+        let column_max = ((column_max_vert_x / cell_width) as i32).min(clip_max.x);
 
         for x in column_min..=column_max {
-            let column_clip_min = x as f32 * nav_mesh_settings.cell_width;
-            let column_clip_max = column_clip_min + nav_mesh_settings.cell_width;
+            let column_clip_min = f64::from(x) * cell_width;
+            let column_clip_max = column_clip_min + cell_width;
 
             // Clip polygon to column.
             let (_, _, column_min_clip_vert_count, column_min_clip_verts) =
@@ -209,10 +557,16 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
                 continue;
             }
 
-            let min_height = (square_min_height / nav_mesh_settings.cell_height) as u16;
-            let max_height = (square_max_height / nav_mesh_settings.cell_height) as u16;
+            let max_height = square_max_height / cell_height;
+            if max_height > f64::from(u16::MAX) {
+                *clamped_above = true;
+            }
+
+            // Casting saturates, anything above the representable height gets squashed into the top.
+            let min_height = (square_min_height / cell_height) as u16;
+            let max_height = max_height as u16;
 
-            let index = x as usize + z as usize * tile_side;
+            let index = (x - cell_offset.x) as usize + (z - cell_offset.z) as usize * row_width;
             let cell = &mut voxel_tile.cells[index];
 
             let mut new_span = HeightSpan {
@@ -220,6 +574,8 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
                 max: max_height,
                 traversable,
                 area,
+                step_height,
+                affector,
             };
 
             if cell.spans.is_empty() {
@@ -238,19 +594,28 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
                     i += 1;
                     continue;
                 }
-                // An overlap!
-                match existing_span.max.cmp(&new_span.max) {
-                    Ordering::Greater => {
-                        new_span.traversable = existing_span.traversable;
+                // An overlap! The more permissive step height wins.
+                new_span.step_height = new_span.step_height.max(existing_span.step_height);
+
+                // Tops this close are the same surface, for example flush colliders where one's side reaches just above the other's top. Otherwise the side would leave a non-traversable seam.
+                // Only done across affectors, a single affector's steep faces ending just above its floor stay non-traversable.
+                let same_surface = existing_span.max == new_span.max
+                    || (existing_span.affector != new_span.affector
+                        && existing_span.max.abs_diff(new_span.max) <= SPAN_MERGE_THRESHOLD);
+
+                if same_surface {
+                    if existing_span.max > new_span.max {
                         new_span.max = existing_span.max;
-                        new_span.area = existing_span.area;
-                    }
-                    Ordering::Equal => {
-                        new_span.traversable |= existing_span.traversable;
-                        // Higher area number has higher priority.
-                        new_span.area = new_span.area.max(existing_span.area);
+                        new_span.affector = existing_span.affector;
                     }
-                    Ordering::Less => {}
+                    new_span.traversable |= existing_span.traversable;
+                    // Higher area number has higher priority.
+                    new_span.area = new_span.area.max(existing_span.area);
+                } else if existing_span.max > new_span.max {
+                    new_span.traversable = existing_span.traversable;
+                    new_span.max = existing_span.max;
+                    new_span.area = existing_span.area;
+                    new_span.affector = existing_span.affector;
                 }
                 cell.spans.remove(i);
             }
@@ -259,13 +624,13 @@ fn process_triangle(a: Vec3, b: Vec3, c: Vec3, nav_mesh_settings: &NavMeshSettin
     }
 }
 
-fn is_triangle_traversable(a: &Vec3, b: &Vec3, c: &Vec3, nav_mesh_settings: &NavMeshSettings) -> bool {
+fn is_triangle_traversable(a: &DVec3, b: &DVec3, c: &DVec3, max_traversable_slope_radians: f32) -> bool {
     let ab = *b - *a;
     let ac = *c - *a;
     let normal = ab.cross(ac).normalize();
-    let slope = normal.dot(Vec3::Y).acos();
+    let slope = normal.dot(DVec3::Y).acos();
     
-    slope < nav_mesh_settings.max_traversable_slope_radians
+    slope < f64::from(max_traversable_slope_radians)
 }
 
 /*
@@ -274,14 +639,14 @@ fn is_triangle_traversable(a: &Vec3, b: &Vec3, c: &Vec3, nav_mesh_settings: &Nav
 *   the right polygon's vertex count, and the right polygon's vertices.
 */
 fn divide_polygon(
-    vertices: &[Vec3; 7], // TODO: Is it even possible to have more than 4 vertices as a result of a single triangle?
+    vertices: &[DVec3; 7], // TODO: Is it even possible to have more than 4 vertices as a result of a single triangle?
     vertex_count_in: usize,
-    clip_line: f32,
+    clip_line: f64,
     axis: usize,
-) -> (usize, [Vec3; 7], usize, [Vec3; 7]) {
+) -> (usize, [DVec3; 7], usize, [DVec3; 7]) {
     // TODO: We always use one of these options. Does it make sense to even return the other?
-    let mut polygon_a = [Vec3::ZERO; 7];
-    let mut polygon_b = [Vec3::ZERO; 7];
+    let mut polygon_a = [DVec3::ZERO; 7];
+    let mut polygon_b = [DVec3::ZERO; 7];
 
     let mut delta_from_line = [0.0; 12];
     // This loop determines which side of the line the vertex is on.
@@ -332,7 +697,7 @@ fn divide_polygon(
 }
 
 pub fn build_open_heightfield_tile(
-    voxelized_tile: VoxelizedTile,
+    voxelized_tile: &VoxelizedTile,
     nav_mesh_settings: &NavMeshSettings,
 ) -> OpenTile {
     let mut cells = vec![OpenCell::default(); voxelized_tile.cells.len()];
@@ -361,6 +726,7 @@ pub fn build_open_heightfield_tile(
                         min: span.max,
                         max: Some(next_span.min),
                         area: span.area,
+                        step_height: span.step_height,
                         ..Default::default()
                     });
                 }
@@ -370,6 +736,7 @@ pub fn build_open_heightfield_tile(
                     min: span.max,
                     max: None,
                     area: span.area,
+                    step_height: span.step_height,
                     ..Default::default()
                 });
             }
@@ -405,10 +772,8 @@ pub fn build_open_heightfield_tile(
 }
 
 fn link_neighbours(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
-    let mut x_positive = Vec::with_capacity(3);
-    let mut x_negative = Vec::with_capacity(3);
-    let mut z_positive = Vec::with_capacity(3);
-    let mut z_negative = Vec::with_capacity(3);
+    // Indexed by direction, see get_neighbour_index.
+    let mut neighbour_spans: [Vec<(u16, Option<u16>, u16)>; 4] = Default::default();
 
     let tile_side = nav_mesh_settings.get_tile_side_with_border();
     for i in 0..open_tile.cells.len() {
@@ -419,122 +784,114 @@ fn link_neighbours(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings
         let row = i / tile_side;
         let column = i % tile_side;
 
-        let x_positive_contained = column < (tile_side - 1);
-        let x_negative_contained = column > 0;
-        let z_positive_contained = row < (tile_side - 1);
-        let z_negative_contained = row > 0;
-
-        x_positive.clear();
-        x_negative.clear();
-        z_positive.clear();
-        z_negative.clear();
-
-        if x_positive_contained {
-            x_positive.extend(
-                open_tile.cells[i + 1]
-                    .spans
-                    .iter()
-                    .map(|span| (span.min, span.max)),
-            );
-        }
-        if x_negative_contained {
-            x_negative.extend(
-                open_tile.cells[i - 1]
-                    .spans
-                    .iter()
-                    .map(|span| (span.min, span.max)),
-            );
-        }
-        if z_positive_contained {
-            z_positive.extend(
-                open_tile.cells[i + tile_side]
-                    .spans
-                    .iter()
-                    .map(|span| (span.min, span.max)),
-            );
-        }
-        if z_negative_contained {
-            z_negative.extend(
-                open_tile.cells[i - tile_side]
-                    .spans
-                    .iter()
-                    .map(|span| (span.min, span.max)),
-            );
-        }
+        let contained = [
+            column > 0,
+            row < (tile_side - 1),
+            column < (tile_side - 1),
+            row > 0,
+        ];
 
-        for span in open_tile.cells[i].spans.iter_mut() {
-            for (i, (min, max)) in x_negative.iter().enumerate() {
-                if max.is_some() && span.max.is_some() {
-                    let max = max.unwrap();
-                    let span_max = span.max.unwrap();
-
-                    let gap = span_max.min(max).abs_diff(span.min.max(*min));
-                    if gap < nav_mesh_settings.walkable_height {
-                        continue;
-                    }
-                }
+        for (dir, spans) in neighbour_spans.iter_mut().enumerate() {
+            spans.clear();
 
-                if min.abs_diff(span.min) < nav_mesh_settings.step_height {
-                    span.neighbours[0] = Some(i as u16);
-                    break;
-                }
+            if contained[dir] {
+                spans.extend(
+                    open_tile.cells[get_neighbour_index(nav_mesh_settings, i, dir)]
+                        .spans
+                        .iter()
+                        .map(|span| (span.min, span.max, span.step_height)),
+                );
             }
+        }
 
-            for (i, (min, max)) in z_positive.iter().enumerate() {
-                if max.is_some() && span.max.is_some() {
-                    let max = max.unwrap();
-                    let span_max = span.max.unwrap();
-
-                    let gap = span_max.min(max).abs_diff(span.min.max(*min));
-                    if gap < nav_mesh_settings.walkable_height {
-                        continue;
-                    }
-                }
-
-                if min.abs_diff(span.min) < nav_mesh_settings.step_height {
-                    span.neighbours[1] = Some(i as u16);
-                    break;
+        for span in open_tile.cells[i].spans.iter_mut() {
+            for (dir, spans) in neighbour_spans.iter().enumerate() {
+                if let Some(neighbour) = spans.iter().position(|(min, max, step_height)| {
+                    can_connect_spans(span.min, span.max, *min, *max, span.step_height.max(*step_height), nav_mesh_settings)
+                }) {
+                    span.neighbours[dir] = Some(neighbour as u16);
                 }
             }
+        }
+    }
+}
 
-            for (i, (min, max)) in x_positive.iter().enumerate() {
-                if max.is_some() && span.max.is_some() {
-                    let max = max.unwrap();
-                    let span_max = span.max.unwrap();
+/// Returns true if an agent can move between two open spans in neighbouring cells.
+///
+/// Symmetric so that spans either link to each other in both directions or not at all.
+/// ``step_height`` is the more permissive step height of the two spans.
+fn can_connect_spans(
+    a_min: u16,
+    a_max: Option<u16>,
+    b_min: u16,
+    b_max: Option<u16>,
+    step_height: u16,
+    nav_mesh_settings: &NavMeshSettings,
+) -> bool {
+    // Unbounded spans are open all the way up.
+    let top = a_max.map_or(u32::MAX, u32::from).min(b_max.map_or(u32::MAX, u32::from));
+    let bottom = u32::from(a_min.max(b_min));
 
-                    let gap = span_max.min(max).abs_diff(span.min.max(*min));
-                    if gap < nav_mesh_settings.walkable_height {
-                        continue;
-                    }
-                }
+    top.saturating_sub(bottom) >= u32::from(nav_mesh_settings.walkable_height)
+        && a_min.abs_diff(b_min) <= step_height
+}
 
-                if min.abs_diff(span.min) < nav_mesh_settings.step_height {
-                    span.neighbours[2] = Some(i as u16);
-                    break;
-                }
-            }
+/// Seed of spans without a wall next to them in [calculate_clearance_field], low enough for [filter_tile] to add to without overflowing.
+const NO_WALL_DISTANCE: u16 = u16::MAX - 3;
+
+/// Calculates the distance from every span to the closest wall or unwalkable span, used for [Polygon::clearance](crate::tiles::Polygon::clearance). Also keeps the spans' step heights for links between tiles.
+///
+/// Unlike [calculate_distance_field] the outer edge of the tile's border isn't a wall, walls beyond the border aren't part of the tile so they aren't seen.
+/// Uses [OpenTile::distances] as scratch space, so it has to run before [erode_walkable_area].
+pub fn calculate_clearance_field(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) -> ClearanceField {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
 
-            for (i, (min, max)) in z_negative.iter().enumerate() {
-                if max.is_some() && span.max.is_some() {
-                    let max = max.unwrap();
-                    let span_max = span.max.unwrap();
+    // Mark cells next to walls.
+    for (i, cell) in open_tile.cells.iter().enumerate() {
+        let (x, z) = (i % tile_side, i / tile_side);
+        // Directions leading out of the tile, in the order of OpenSpan::neighbours.
+        let leaves_tile = [x == 0, z == tile_side - 1, x == tile_side - 1, z == 0];
 
-                    let gap = span_max.min(max).abs_diff(span.min.max(*min));
-                    if gap < nav_mesh_settings.walkable_height {
-                        continue;
+        for span in cell.spans.iter() {
+            let next_to_wall = open_tile.areas[span.tile_index].is_none()
+                || span.neighbours.iter().enumerate().any(|(dir, neighbour)| {
+                    if let Some(neighbour) = neighbour {
+                        let neighbour_index = get_neighbour_index(nav_mesh_settings, i, dir);
+                        let neighbour = &open_tile.cells[neighbour_index].spans[*neighbour as usize];
+
+                        open_tile.areas[neighbour.tile_index].is_none()
+                    } else {
+                        !leaves_tile[dir]
                     }
-                }
+                });
 
-                if min.abs_diff(span.min) < nav_mesh_settings.step_height {
-                    span.neighbours[3] = Some(i as u16);
-                    break;
-                }
-            }
+            open_tile.distances[span.tile_index] = if next_to_wall { 0 } else { NO_WALL_DISTANCE };
         }
     }
+
+    filter_tile(open_tile, nav_mesh_settings);
+
+    let mut cell_starts = Vec::with_capacity(open_tile.cells.len() + 1);
+    let mut spans = Vec::with_capacity(open_tile.span_count);
+    for cell in open_tile.cells.iter() {
+        cell_starts.push(spans.len());
+        spans.extend(cell.spans.iter().map(|span| ClearanceSpan {
+            floor: span.min,
+            distance: open_tile.distances[span.tile_index],
+            step_height: span.step_height,
+        }));
+    }
+    cell_starts.push(spans.len());
+
+    ClearanceField { cell_starts, spans }
 }
 
 pub fn erode_walkable_area(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
+    // Nothing would be eroded, the distance field is calculated from scratch afterwards.
+    if nav_mesh_settings.walkable_radius == 0 {
+        return;
+    }
+
     // Mark boundary cells.
     for (i, cell) in open_tile.cells.iter().enumerate() {
         for span in cell.spans.iter() {
@@ -4,7 +4,7 @@
 //!
 //! ## Quick Start:
 //! **Nav-mesh generation:**
-//! 1. Add [OxidizedNavigationPlugin] as a plugin.
+//! 1. Add [OxidizedNavigationPlugin] as a plugin. (``OxidizedNavigationPlugin::new(settings)``)
 //! 2. Attach a [NavMeshAffector] component and a rapier collider to any entity you want to affect the nav-mesh.
 //!
 //! *At this point nav-meshes will be automatically generated whenever the collider or [GlobalTransform] of any entity with a [NavMeshAffector] is changed.*
@@ -21,30 +21,34 @@
 //! [Bevy Rapier3D]: https://crates.io/crates/bevy_rapier3d
 //! [examples]: https://github.com/TheGrimsey/oxidized_navigation/blob/master/examples
 
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
-use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::tasks::{AsyncComputeTaskPool, ComputeTaskPool, Task, TaskPool};
 use bevy::{
     ecs::system::Resource,
     prelude::*,
-    utils::{HashMap, HashSet},
+    utils::{Duration, HashMap, HashSet, Instant},
 };
+#[cfg(feature = "trace")]
+use bevy::utils::tracing::field;
 use bevy_rapier3d::prelude::ColliderView;
 use bevy_rapier3d::rapier::prelude::HeightField;
 use bevy_rapier3d::{na::Vector3, prelude::Collider, rapier::prelude::Isometry};
 use contour::build_contours;
 use conversion::{GeometryToConvert, ColliderType, convert_geometry_collections, GeometryCollection};
 use heightfields::{
-    build_heightfield_tile, build_open_heightfield_tile, calculate_distance_field,
-    erode_walkable_area, HeightFieldCollection,
+    build_heightfield_block, build_heightfield_tile, build_open_heightfield_tile, calculate_clearance_field,
+    calculate_distance_field, erode_walkable_area, CellRange, HeightFieldCollection, OpenTile, VoxelizedTile,
 };
 use mesher::build_poly_mesh;
 use regions::build_regions;
 use smallvec::SmallVec;
-use tiles::{create_nav_mesh_tile_from_poly_mesh, NavMeshTiles};
+use tiles::{create_nav_mesh_tile_from_poly_mesh, NavMeshTile, NavMeshTiles};
 
 mod conversion;
 mod contour;
+#[cfg(feature = "debug_draw")]
+pub mod debug_draw;
 mod heightfields;
 mod mesher;
 pub mod query;
@@ -52,6 +56,9 @@ mod regions;
 pub mod tiles;
 
 /// System sets containing the crate's systems.
+///
+/// Every system added by [OxidizedNavigationPlugin] is in one of these sets, which are placed in the plugin's [OxidizedNavigationPlugin::base_set].
+/// Order your own systems against these sets, for example ``.before(OxidizedNavigation::Main)`` for systems spawning or moving affectors.
 #[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone)]
 pub enum OxidizedNavigation {
     /// Systems handling dirty marking when a NavMeshAffector component is removed.
@@ -62,19 +69,62 @@ pub enum OxidizedNavigation {
 }
 
 pub struct OxidizedNavigationPlugin {
-    pub settings: NavMeshSettings
+    pub settings: NavMeshSettings,
+    /// Base set the [OxidizedNavigation] system sets are run in. [OxidizedNavigationPlugin::new] defaults to [CoreSet::Update].
+    ///
+    /// Use [CoreSet::PostUpdate] & order [OxidizedNavigation::Main] after transform propagation & rapier's writeback if generation should see the same frame's changes.
+    pub base_set: CoreSet,
+    /// Task pool tile generation tasks are spawned on. [OxidizedNavigationPlugin::new] defaults to [GenerationTaskPool::AsyncCompute].
+    pub task_pool: GenerationTaskPool,
+}
+
+impl OxidizedNavigationPlugin {
+    /// Creates the plugin running in [CoreSet::Update].
+    pub fn new(settings: NavMeshSettings) -> Self {
+        Self {
+            settings,
+            base_set: CoreSet::Update,
+            task_pool: GenerationTaskPool::AsyncCompute,
+        }
+    }
 }
 
 impl Plugin for OxidizedNavigationPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(self.settings.clone());
+        if self.settings.up.try_normalize().is_none() {
+            warn!("NavMeshSettings::up is {}, which isn't a direction. Using Vec3::Y instead.", self.settings.up);
+        }
+
+        app.insert_resource(self.settings.clone())
+            .insert_resource(self.task_pool.clone());
+
+        app.configure_sets(
+            (OxidizedNavigation::RemovedComponent, OxidizedNavigation::Main)
+                .in_base_set(self.base_set.clone()),
+        );
 
         app.init_resource::<TileAffectors>()
             .init_resource::<DirtyTiles>()
             .init_resource::<NavMesh>()
             .init_resource::<GenerationTicker>()
             .init_resource::<NavMeshAffectorRelations>()
-            .init_resource::<ActiveGenerationTasks>();
+            .init_resource::<ActiveGenerationTasks>()
+            .init_resource::<VoxelizedTileCache>()
+            .init_resource::<NavMeshGenerationState>();
+
+        #[cfg(feature = "debug_draw")]
+        {
+            app.init_resource::<debug_draw::NavMeshDebugStage>()
+                .init_resource::<debug_draw::NavMeshDebugArtifacts>();
+
+            app.add_system(
+                debug_draw::update_debug_tiles_system
+                    .in_set(OxidizedNavigation::Main)
+                    .before(send_tile_rebuild_tasks_system),
+            );
+
+            app.add_system(debug_draw::draw_nav_mesh_debug_stage_system.in_set(OxidizedNavigation::Main));
+        }
 
         app.add_system(
             handle_removed_affectors_system
@@ -82,10 +132,30 @@ impl Plugin for OxidizedNavigationPlugin {
                 .in_set(OxidizedNavigation::RemovedComponent)
         );
 
+        app.add_system(
+            handle_disabled_affectors_system
+                .before(update_navmesh_affectors_system)
+                .before(send_tile_rebuild_tasks_system)
+                .in_set(OxidizedNavigation::RemovedComponent)
+        );
+
+        app.add_system(
+            handle_removed_overrides_system
+                .after(handle_removed_affectors_system)
+                .before(send_tile_rebuild_tasks_system)
+                .in_set(OxidizedNavigation::RemovedComponent)
+        );
+
         app.add_system(
             remove_finished_tasks.in_set(OxidizedNavigation::Main).before(send_tile_rebuild_tasks_system),
         );
 
+        app.add_system(
+            recover_poisoned_nav_mesh_system
+                .in_set(OxidizedNavigation::Main)
+                .before(send_tile_rebuild_tasks_system),
+        );
+
         app.add_systems(
             (
                 update_navmesh_affectors_system,
@@ -97,24 +167,108 @@ impl Plugin for OxidizedNavigationPlugin {
     }
 }
 
+/// Task pool that nav-mesh tiles are generated on.
+///
+/// Choosing a pool trades generation latency against frame-time impact:
+/// - A pool shared with other work gets tiles out quickly, but a burst of generation can delay that other work. ([GenerationTaskPool::AsyncCompute] & [GenerationTaskPool::Compute])
+/// - A dedicated pool with few threads bounds how much of the CPU generation can take at once, but tiles take longer to be generated when many are dirty. ([GenerationTaskPool::Custom])
+///
+/// This is separate from [NavMeshSettings::max_tile_generation_tasks] which limits how many tiles are in flight (and thus memory), not how many threads they run on.
+#[derive(Resource, Clone, Default)]
+pub enum GenerationTaskPool {
+    /// Bevy's [AsyncComputeTaskPool]. Shared with any other async tasks in the app.
+    #[default]
+    AsyncCompute,
+    /// Bevy's [ComputeTaskPool]. Tasks compete with systems running in the frame, which may increase frame times.
+    Compute,
+    /// A dedicated pool, for example one created using [TaskPoolBuilder](bevy::tasks::TaskPoolBuilder) with a limited ``num_threads``.
+    Custom(Arc<TaskPool>),
+}
+
+impl GenerationTaskPool {
+    /// Returns the underlying [TaskPool].
+    pub fn get(&self) -> &TaskPool {
+        match self {
+            GenerationTaskPool::AsyncCompute => AsyncComputeTaskPool::get(),
+            GenerationTaskPool::Compute => ComputeTaskPool::get(),
+            GenerationTaskPool::Custom(task_pool) => task_pool,
+        }
+    }
+}
+
 const FLAG_BORDER_VERTEX: u32 = 0x10000;
 const MASK_CONTOUR_REGION: u32 = 0xffff; // Masks out the above value.
 
 #[derive(Resource, Default)]
-struct NavMeshAffectorRelations(HashMap<Entity, SmallVec<[UVec2; 4]>>);
+struct NavMeshAffectorRelations(HashMap<Entity, AffectorRelation>);
 
+struct AffectorRelation {
+    tiles: SmallVec<[UVec2; 4]>,
+    /// Bounds of the affector when it was last updated. Used to tell which part of a tile changed when the affector moves or is removed.
+    bounds: AreaBounds,
+}
+
+/// Tile generation tasks currently running & the tiles each of them generates. Read-only, for tooling such as debug panels.
 #[derive(Resource, Default)]
-struct ActiveGenerationTasks(Vec<Task<()>>);
+pub struct ActiveGenerationTasks(Vec<(Task<()>, Vec<UVec2>)>);
+
+impl ActiveGenerationTasks {
+    /// Returns the number of running tasks. This is what's limited by [NavMeshSettings::max_tile_generation_tasks].
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the tiles being generated by running tasks. A task generates several tiles if [NavMeshSettings::tile_batch_size] is set.
+    ///
+    /// Tiles being removed aren't included as removing a tile doesn't count as a generation task.
+    pub fn get_tiles(&self) -> impl Iterator<Item = UVec2> + '_ {
+        self.0.iter().flat_map(|(_, tiles)| tiles.iter().copied())
+    }
+}
 
 /// Component for entities that should affect the nav-mesh.
 #[derive(Component)]
 pub struct NavMeshAffector;
 
+/// Optional component to temporarily stop a [NavMeshAffector] from affecting the nav-mesh, without removing it.
+///
+/// Inserting this has the same effect on the nav-mesh as removing the [NavMeshAffector] component. Removing it restores the entity's contribution.
+#[derive(Component)]
+pub struct NavMeshAffectorDisabled;
+
 /// Optional component to define the area type of an entity. Setting this to ``None`` means that the entity isn't walkable.
 ///
 /// Any part of the nav-mesh generated from this entity will have this area type. Overlapping areas will prefer the higher area type.
 #[derive(Component)]
-pub struct NavMeshAreaType(Option<u16>);
+pub struct NavMeshAreaType(pub Option<u16>);
+
+/// Optional component to override some of the [NavMeshSettings] for the geometry of a [NavMeshAffector]. For example allowing larger steps on stairs.
+///
+/// Fields set to ``None`` & affectors without this component use the global [NavMeshSettings].
+/// Where geometry from several affectors overlaps in the same cell the larger step height is used, while the slope only decides whether the affector's own triangles are walkable so the surface on top decides whether the cell is.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct NavMeshAffectorOverrides {
+    /// Overrides [NavMeshSettings::max_traversable_slope_radians] for the affector's triangles.
+    pub max_traversable_slope_radians: Option<f32>,
+    /// Overrides [NavMeshSettings::step_height] for steps onto or off of the affector's surface.
+    ///
+    /// Also applies to connections between tiles, where a polygon edge on the tile's border uses the largest step height of the spans along it.
+    pub step_height: Option<u16>,
+}
+
+/// Resource controlling whether new tiles are generated. Insert [NavMeshGenerationState::Paused] to stop generating tiles, for example during cutscenes or loading screens.
+///
+/// Affectors are still tracked while paused, any tiles they dirty are generated once generation is set back to [NavMeshGenerationState::Running]. Tiles already being generated when pausing are still finished.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NavMeshGenerationState {
+    #[default]
+    Running,
+    Paused,
+}
 
 /*
 *   Neighbours:
@@ -126,16 +280,89 @@ pub struct NavMeshAreaType(Option<u16>);
 
 /// Generation ticker for tiles.
 ///
-/// Used to keep track of if the existing tile is newer than the one we are trying to insert in [build_tile]. This could happen if we go from having a lot of triangles to very few.
+/// Used to keep track of if the existing tile is newer than the one a tile task is trying to insert. This could happen if we go from having a lot of triangles to very few.
 #[derive(Default, Resource)]
-struct GenerationTicker(u64);
+pub struct GenerationTicker(u64);
+
+impl GenerationTicker {
+    /// Returns the generation given to the most recently spawned tile task.
+    ///
+    /// Compare with [NavMeshTiles::get_tile_generation] to tell whether a tile is from before or after a point in time.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
 
 #[derive(Default, Resource, Deref, DerefMut)]
 struct TileAffectors(HashMap<UVec2, HashSet<Entity>>);
 
-/// Set of all tiles that need to be rebuilt.
+/// All tiles that need to be rebuilt & the area within them that changed. Read-only, for tooling such as debug panels.
 #[derive(Default, Resource)]
-struct DirtyTiles(HashSet<UVec2>);
+pub struct DirtyTiles(HashMap<UVec2, AreaBounds>);
+
+impl DirtyTiles {
+    /// Returns the tiles waiting to be rebuilt.
+    pub fn get_tiles(&self) -> impl Iterator<Item = UVec2> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// Returns true if ``tile`` is waiting to be rebuilt.
+    pub fn contains(&self, tile: UVec2) -> bool {
+        self.0.contains_key(&tile)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Marks a tile as dirty, growing the tile's changed area to include ``area``.
+    fn mark(&mut self, tile: UVec2, area: AreaBounds) {
+        self.0
+            .entry(tile)
+            .and_modify(|existing| *existing = existing.union(area))
+            .or_insert(area);
+    }
+
+    /// Marks the whole of a tile, including its border, as dirty.
+    fn mark_tile(&mut self, tile: UVec2, nav_mesh_settings: &NavMeshSettings) {
+        let border_size = nav_mesh_settings.get_border_size();
+        let (min, max) = nav_mesh_settings.get_tile_bounds(tile);
+
+        self.mark(
+            tile,
+            AreaBounds {
+                min: min - border_size,
+                max: max + border_size,
+            },
+        );
+    }
+}
+
+/// Bounds on the XZ-plane in world units.
+#[derive(Clone, Copy, Debug)]
+struct AreaBounds {
+    min: Vec2,
+    max: Vec2,
+}
+
+impl AreaBounds {
+    fn union(self, other: AreaBounds) -> AreaBounds {
+        AreaBounds {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+/// Voxelized tiles kept for [NavMeshSettings::experimental_incremental_voxelization] along with the generation they were built in.
+///
+/// Shared with the generation tasks which insert their tile once voxelized.
+#[derive(Default, Resource, Clone)]
+struct VoxelizedTileCache(Arc<Mutex<HashMap<UVec2, (u64, VoxelizedTile)>>>);
 
 /// Settings for nav-mesh generation.
 #[derive(Resource, Clone)]
@@ -166,10 +393,17 @@ pub struct NavMeshSettings {
     ///
     /// This exists because figuring out which tile we are in around the world origin would not work without it.
     pub world_half_extents: f32,
-    /// Bottom extents of the world on the Y-axis. The top extents is capped by ``world_bottom_bound + cell_height * u16::MAX``.
+    /// Bottom extents of the world on the Y-axis. The top extents is capped by ``world_bottom_bound + cell_height * u16::MAX``, see [NavMeshSettings::max_representable_height].
     ///
     /// **Suggested value**: Minium Y position of anything in the world that should be covered by the nav mesh.
     pub world_bottom_bound: f32,
+    /// Direction opposite of gravity, walkable surfaces face this way. ``Vec3::Y`` is used instead if it's zero or not finite.
+    ///
+    /// **Suggested value**: ``Vec3::Y``. ``Vec3::Z`` for Z-up worlds.
+    ///
+    /// Geometry is rotated into nav-mesh space, where ``up`` points along +Y, before being voxelized. All settings referring to the Y-axis or the XZ-plane, including the tiling, refer to nav-mesh space.
+    /// Queries in [query] taking [NavMeshSettings] convert positions to & from world space. [NavMeshTiles] & [NavMeshTile] data is stored in nav-mesh space, use [NavMeshSettings::to_world_space] to convert it.
+    pub up: Vec3,
 
     /// Maximum incline/slope traversable when navigating in radians.
     pub max_traversable_slope_radians: f32,
@@ -180,8 +414,12 @@ pub struct NavMeshSettings {
     /// This will "pull-back" the nav-mesh from edges, meaning anywhere on the nav-mesh will be walkable for a character with a radius of ``walkable_radius * cell_width``.
     ///
     /// **Suggested value**: ``ceil(character_radius / cell_width)`` (2-3 if `cell_width`` is 1/2 of ``character_radius``)  
+    ///
+    /// ``0`` skips erosion & the tile border, producing a nav-mesh covering the whole walkable surface. Useful when baking a separate nav-mesh per agent radius.
     pub walkable_radius: u16,
     /// Maximum height difference that is still considered traversable in cell_height(s). (Think, stair steps)
+    ///
+    /// Inclusive, a step of exactly ``step_height`` is traversable both up & down.
     pub step_height: u16,
 
     /// Minimum size of a region, anything smaller than this will be removed. This is used to filter out smaller regions that might appear on tables.
@@ -196,12 +434,35 @@ pub struct NavMeshSettings {
     /// Maximum difference allowed for simplified contour generation on the XZ-plane in cell_width(s).
     ///
     /// **Suggested value range**: [1.1, 1.5]
+    ///
+    /// ``0.0`` keeps every vertex that isn't on a straight line. Negative values are treated as ``0.0``.
     pub max_contour_simplification_error: f32,
+    /// Skips simplifying contours, keeping every vertex of the voxelized outline. [NavMeshSettings::max_contour_simplification_error] & [NavMeshSettings::max_edge_length] are ignored.
+    ///
+    /// **Suggested value**: ``false``. Meant for debugging & tuning [NavMeshSettings::max_contour_simplification_error], results in a lot more polygons & less direct paths.
+    pub raw_contours: bool,
+    /// Maximum vertices in a polygon. Adjacent triangles from the same region are merged into convex polygons of up to this many vertices.
+    ///
+    /// **Suggested value range**: [3, 6] Values outside of this range are clamped. ``3`` results in only triangles.
+    ///
+    /// Fewer, larger polygons shrink the graph searched when pathfinding. Triangles are only merged if the result stays within one ``cell_height`` of being planar.
+    pub max_vertices_per_polygon: usize,
 
     /// Optional max tiles to generate at once. A value of ``None`` will result in no limit.
     /// 
     /// Adjust this to control memory & CPU usage. More tiles generating at once will have a higher memory footprint.
     pub max_tile_generation_tasks: Option<u16>,
+    /// Optional max width & depth in tiles of a block of adjacent dirty tiles to generate in a single task. A value of ``None`` or ``1`` generates every tile in its own task.
+    ///
+    /// The block's geometry is gathered & voxelized once instead of once per tile, which saves duplicate work when large affectors span several tiles at the cost of fewer, longer tasks.
+    /// Tiles are generated exactly as they would be on their own. Batched tiles are always fully voxelized, even with [NavMeshSettings::experimental_incremental_voxelization]. Tiles selected for debug drawing are never batched.
+    pub tile_batch_size: Option<u16>,
+
+    /// **Experimental.** Keeps the voxelized version of every tile around & only re-voxelizes the cells covered by the affectors that changed when rebuilding a tile.
+    ///
+    /// Later stages (distance field, regions, contours & polygons) are still rebuilt for the whole tile, so the result matches a full rebuild.
+    /// This mostly helps tiles dominated by large static geometry where small affectors move around, at the cost of keeping a voxelized tile in memory for every tile.
+    pub experimental_incremental_voxelization: bool,
 }
 impl NavMeshSettings {
     /// Returns the length of a tile's side in world units.
@@ -209,6 +470,33 @@ impl NavMeshSettings {
     pub fn get_tile_size(&self) -> f32 {
         self.cell_width * f32::from(self.tile_width)
     }
+    /// Returns the highest point on the Y-axis the nav-mesh can represent in world units.
+    ///
+    /// Span heights are stored as [u16] cell heights above [NavMeshSettings::world_bottom_bound], geometry above this is clamped & a warning is logged.
+    #[inline]
+    pub fn max_representable_height(&self) -> f32 {
+        self.world_bottom_bound + self.cell_height * f32::from(u16::MAX)
+    }
+    /// Returns [NavMeshSettings::up] normalized, falling back to ``Vec3::Y`` if it's zero or not finite.
+    #[inline]
+    pub fn get_up(&self) -> Vec3 {
+        self.up.try_normalize().unwrap_or(Vec3::Y)
+    }
+    /// Returns the rotation from world space into nav-mesh space, where [NavMeshSettings::get_up] points along +Y.
+    #[inline]
+    pub fn get_up_rotation(&self) -> Quat {
+        Quat::from_rotation_arc(self.get_up(), Vec3::Y)
+    }
+    /// Converts ``world_position`` into nav-mesh space.
+    #[inline]
+    pub fn to_nav_mesh_space(&self, world_position: Vec3) -> Vec3 {
+        self.get_up_rotation() * world_position
+    }
+    /// Converts ``nav_mesh_position`` from nav-mesh space back into world space.
+    #[inline]
+    pub fn to_world_space(&self, nav_mesh_position: Vec3) -> Vec3 {
+        self.get_up_rotation().inverse() * nav_mesh_position
+    }
     #[inline]
     pub fn get_border_size(&self) -> f32 {
         f32::from(self.walkable_radius) * self.cell_width
@@ -254,6 +542,17 @@ impl NavMeshSettings {
 
         (min_bound, max_bound)
     }
+
+    /// Returns the cells (including the border) of a tile that overlap ``area``.
+    fn get_cells_in_area(&self, tile: UVec2, area: AreaBounds) -> CellRange {
+        let tile_origin = self.get_tile_origin_with_border(tile);
+
+        // Grown by a cell to not miss any cell due to precision.
+        CellRange {
+            min: ((area.min - tile_origin) / self.cell_width).floor().as_ivec2() - 1,
+            max: ((area.max - tile_origin) / self.cell_width).floor().as_ivec2() + 1,
+        }
+    }
 }
 
 /// Wrapper around the nav-mesh data.
@@ -266,221 +565,374 @@ impl NavMesh {
     pub fn get(&self) -> Arc<RwLock<NavMeshTiles>> {
         self.0.clone()
     }
+
+    /// Returns true if a thread panicked while holding the nav-mesh lock.
+    ///
+    /// The plugin recovers a poisoned nav-mesh automatically, see [NavMesh::recover].
+    pub fn is_poisoned(&self) -> bool {
+        self.0.is_poisoned()
+    }
+
+    /// Clears the poison from the nav-mesh lock & resets it to an empty nav-mesh as the data may have been left in an invalid state.
+    ///
+    /// This only resets the nav-mesh, the plugin regenerates all tiles when it recovers the nav-mesh itself.
+    pub fn recover(&self) {
+        let mut nav_mesh = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        *nav_mesh = NavMeshTiles::default();
+
+        self.0.clear_poison();
+    }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_navmesh_affectors_system(
     nav_mesh_settings: Res<NavMeshSettings>,
     mut tile_affectors: ResMut<TileAffectors>,
     mut affector_relations: ResMut<NavMeshAffectorRelations>,
     mut dirty_tiles: ResMut<DirtyTiles>,
-    mut query: Query<
+    query: Query<
         (Entity, &Collider, &GlobalTransform),
-        (Or<(Changed<GlobalTransform>, Changed<Collider>, Changed<NavMeshAffector>)>, With<NavMeshAffector>)
+        (
+            Or<(
+                Changed<GlobalTransform>,
+                Changed<Collider>,
+                Changed<NavMeshAffector>,
+                Changed<NavMeshAffectorOverrides>,
+            )>,
+            With<NavMeshAffector>,
+            Without<NavMeshAffectorDisabled>,
+        ),
     >,
 ) {
+    query.for_each(|(e, collider, global_transform)| {
+        update_affector_tiles(
+            e,
+            collider,
+            global_transform,
+            &nav_mesh_settings,
+            &mut tile_affectors,
+            &mut affector_relations,
+            &mut dirty_tiles,
+        );
+    });
+}
+
+/// Updates which tiles an affector is part of & marks any tiles it entered or left as dirty.
+fn update_affector_tiles(
+    e: Entity,
+    collider: &Collider,
+    global_transform: &GlobalTransform,
+    nav_mesh_settings: &NavMeshSettings,
+    tile_affectors: &mut TileAffectors,
+    affector_relations: &mut NavMeshAffectorRelations,
+    dirty_tiles: &mut DirtyTiles,
+) {
+    let (bounds, min_tile, max_tile) = get_affector_tile_coverage(collider, global_transform, nav_mesh_settings);
+
+    let (relation, dirty_area) = if let Some(relation) = affector_relations.0.get_mut(&e) {
+        // Both where the affector was & where it is now may have changed.
+        let dirty_area = relation.bounds.union(bounds);
+
+        // Remove from previous.
+        for old_tile in relation.tiles.iter().filter(|tile_coord| {
+            min_tile.x > tile_coord.x
+                || min_tile.y > tile_coord.y
+                || max_tile.x < tile_coord.x
+                || max_tile.y < tile_coord.y
+        }) {
+            if let Some(affectors) = tile_affectors.get_mut(old_tile) {
+                affectors.remove(&e);
+                dirty_tiles.mark(*old_tile, relation.bounds);
+            }
+        }
+        relation.tiles.clear();
+        relation.bounds = bounds;
+
+        (relation, dirty_area)
+    } else {
+        let relation = AffectorRelation {
+            tiles: SmallVec::default(),
+            bounds,
+        };
+
+        (affector_relations.0.insert_unique_unchecked(e, relation).1, bounds)
+    };
+
+    for x in min_tile.x..=max_tile.x {
+        for y in min_tile.y..=max_tile.y {
+            let tile_coord = UVec2::new(x, y);
+
+            let affectors = if let Some(affectors) = tile_affectors.get_mut(&tile_coord) {
+                affectors
+            } else {
+                tile_affectors.insert_unique_unchecked(tile_coord, HashSet::default()).1
+            };
+            affectors.insert(e);
+
+            relation.tiles.push(tile_coord);
+            dirty_tiles.mark(tile_coord, dirty_area);
+        }
+    }
+}
+
+/// Returns the bounds of an affector along with the minimum & maximum tile it affects.
+fn get_affector_tile_coverage(
+    collider: &Collider,
+    global_transform: &GlobalTransform,
+    nav_mesh_settings: &NavMeshSettings,
+) -> (AreaBounds, UVec2, UVec2) {
     // Expand by 2 * walkable_radius to match with erode_walkable_area.
     let border_expansion =
         f32::from(nav_mesh_settings.walkable_radius * 2) * nav_mesh_settings.cell_width;
-    
-    query.for_each_mut(|(e, collider, global_transform)| {
-        let transform = global_transform.compute_transform();
-        let iso = Isometry::new(
-            transform.translation.into(),
-            transform.rotation.to_scaled_axis().into(),
-        );
-        let local_aabb = collider.raw.compute_local_aabb();
-        let aabb = local_aabb
-            .scaled(&Vector3::new(
-                transform.scale.x,
-                transform.scale.y,
-                transform.scale.z,
-            ))
-            .transform_by(&iso);
-
-        let min_vec = Vec2::new(
-            aabb.mins.x - border_expansion,
-            aabb.mins.z - border_expansion,
-        );
-        let min_tile = nav_mesh_settings.get_tile_containing_position(min_vec);
 
-        let max_vec = Vec2::new(
-            aabb.maxs.x + border_expansion,
-            aabb.maxs.z + border_expansion,
-        );
-        let max_tile = nav_mesh_settings.get_tile_containing_position(max_vec);
-
-        let relation = if let Some(relation) = affector_relations.0.get_mut(&e) {
-            // Remove from previous.
-            for old_tile in relation.iter().filter(|tile_coord| {
-                min_tile.x > tile_coord.x
-                    || min_tile.y > tile_coord.y
-                    || max_tile.x < tile_coord.x
-                    || max_tile.y < tile_coord.y
-            }) {
-                if let Some(affectors) = tile_affectors.get_mut(old_tile) {
-                    affectors.remove(&e);
-                    dirty_tiles.0.insert(*old_tile);
-                }
-            }
-            relation.clear();
+    let transform = get_nav_mesh_space_transform(global_transform, nav_mesh_settings);
+    let iso = Isometry::new(
+        transform.translation.into(),
+        transform.rotation.to_scaled_axis().into(),
+    );
+    // Rapier may have already applied a scale to the collider, undo it so that the transform's scale is applied exactly once like during conversion.
+    let scale = transform.scale / collider.scale();
+    let local_aabb = collider.raw.compute_local_aabb();
+    let aabb = local_aabb
+        .scaled(&Vector3::new(scale.x, scale.y, scale.z))
+        .transform_by(&iso);
+
+    let bounds = AreaBounds {
+        min: Vec2::new(aabb.mins.x, aabb.mins.z),
+        max: Vec2::new(aabb.maxs.x, aabb.maxs.z),
+    };
 
-            relation
-        } else {
-            affector_relations.0.insert_unique_unchecked(e, SmallVec::default()).1
-        };
-        
-        for x in min_tile.x..=max_tile.x {
-            for y in min_tile.y..=max_tile.y {
-                let tile_coord = UVec2::new(x, y);
+    let min_tile = nav_mesh_settings.get_tile_containing_position(bounds.min - border_expansion);
+    let max_tile = nav_mesh_settings.get_tile_containing_position(bounds.max + border_expansion);
 
-                let affectors = if let Some(affectors) = tile_affectors.get_mut(&tile_coord) {
-                    affectors
-                } else {
-                    tile_affectors.insert_unique_unchecked(tile_coord, HashSet::default()).1
-                };
-                affectors.insert(e);
+    (bounds, min_tile, max_tile)
+}
 
-                relation.push(tile_coord);
-                dirty_tiles.0.insert(tile_coord);
-            }
+/// Returns ``global_transform`` moved into nav-mesh space, see [NavMeshSettings::up].
+fn get_nav_mesh_space_transform(global_transform: &GlobalTransform, nav_mesh_settings: &NavMeshSettings) -> Transform {
+    let up_rotation = nav_mesh_settings.get_up_rotation();
+    let transform = global_transform.compute_transform();
+
+    Transform {
+        translation: up_rotation * transform.translation,
+        rotation: up_rotation * transform.rotation,
+        scale: transform.scale,
+    }
+}
+
+/// Removes an affector from all tiles it was part of, marking those tiles as dirty.
+fn remove_affector_from_tiles(
+    e: Entity,
+    relation: &AffectorRelation,
+    tile_affectors: &mut TileAffectors,
+    dirty_tiles: &mut DirtyTiles,
+) {
+    for tile in relation.tiles.iter() {
+        if let Some(affectors) = tile_affectors.get_mut(tile) {
+            affectors.remove(&e);
         }
-    });
+
+        dirty_tiles.mark(*tile, relation.bounds);
+    }
 }
 
+/// Handles [NavMeshAffector] being removed, including when the entity is despawned.
 fn handle_removed_affectors_system(
     mut removed_affectors: RemovedComponents<NavMeshAffector>,
+    mut tile_affectors: ResMut<TileAffectors>,
     mut affector_relations: ResMut<NavMeshAffectorRelations>,
     mut dirty_tiles: ResMut<DirtyTiles>,
 ) {
-    for relations in removed_affectors.iter().filter_map(|removed| affector_relations.0.remove(&removed)) {
-        for tile in relations {
-            dirty_tiles.0.insert(tile);
+    for e in removed_affectors.iter() {
+        if let Some(relation) = affector_relations.0.remove(&e) {
+            remove_affector_from_tiles(e, &relation, &mut tile_affectors, &mut dirty_tiles);
         }
     }
 }
 
+/// Handles [NavMeshAffectorOverrides] being removed from an affector, its geometry goes back to using the global [NavMeshSettings].
+fn handle_removed_overrides_system(
+    mut removed_overrides: RemovedComponents<NavMeshAffectorOverrides>,
+    affector_relations: Res<NavMeshAffectorRelations>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+) {
+    for e in removed_overrides.iter() {
+        // Despawned & disabled affectors have no relation, their tiles are already dirtied when removing them.
+        let Some(relation) = affector_relations.0.get(&e) else {
+            continue;
+        };
+
+        for tile in relation.tiles.iter() {
+            dirty_tiles.mark(*tile, relation.bounds);
+        }
+    }
+}
+
+/// Handles [NavMeshAffectorDisabled] being inserted or removed.
+///
+/// Lives in [OxidizedNavigation::RemovedComponent] as re-enabling an affector is detected through [RemovedComponents].
+#[allow(clippy::type_complexity)]
+fn handle_disabled_affectors_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    mut tile_affectors: ResMut<TileAffectors>,
+    mut affector_relations: ResMut<NavMeshAffectorRelations>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+    disabled_query: Query<Entity, (With<NavMeshAffector>, Added<NavMeshAffectorDisabled>)>,
+    mut enabled_affectors: RemovedComponents<NavMeshAffectorDisabled>,
+    enabled_query: Query<
+        (&Collider, &GlobalTransform),
+        (With<NavMeshAffector>, Without<NavMeshAffectorDisabled>),
+    >,
+) {
+    for e in disabled_query.iter() {
+        if let Some(relation) = affector_relations.0.remove(&e) {
+            remove_affector_from_tiles(e, &relation, &mut tile_affectors, &mut dirty_tiles);
+        }
+    }
+
+    for e in enabled_affectors.iter() {
+        let Ok((collider, global_transform)) = enabled_query.get(e) else {
+            continue;
+        };
+
+        update_affector_tiles(
+            e,
+            collider,
+            global_transform,
+            &nav_mesh_settings,
+            &mut tile_affectors,
+            &mut affector_relations,
+            &mut dirty_tiles,
+        );
+    }
+}
+
+#[allow(clippy::unnecessary_map_or)]
 fn can_generate_new_tiles(
     active_generation_tasks: Res<ActiveGenerationTasks>,
     dirty_tiles: Res<DirtyTiles>,
     nav_mesh_settings: Res<NavMeshSettings>,
+    generation_state: Res<NavMeshGenerationState>,
 ) -> bool {
-    nav_mesh_settings.max_tile_generation_tasks.map_or(true, |max_tile_generation_tasks| active_generation_tasks.0.len() < max_tile_generation_tasks.into())
+    *generation_state == NavMeshGenerationState::Running
+        && nav_mesh_settings.max_tile_generation_tasks.map_or(true, |max_tile_generation_tasks| active_generation_tasks.0.len() < max_tile_generation_tasks.into())
         && !dirty_tiles.0.is_empty()
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn send_tile_rebuild_tasks_system(
     mut active_generation_tasks: ResMut<ActiveGenerationTasks>,
     mut generation_ticker: ResMut<GenerationTicker>,
     mut dirty_tiles: ResMut<DirtyTiles>,
-    mut tiles_to_generate: Local<Vec<UVec2>>,
+    mut tiles_to_generate: Local<Vec<(UVec2, AreaBounds)>>,
     mut heightfields: Local<HashMap<Entity, Arc<HeightField>>>,
+    mut last_tile_generations: Local<HashMap<UVec2, u64>>,
+    voxelized_tile_cache: Res<VoxelizedTileCache>,
     nav_mesh_settings: Res<NavMeshSettings>,
+    task_pool: Res<GenerationTaskPool>,
     nav_mesh: Res<NavMesh>,
     tile_affectors: Res<TileAffectors>,
     collider_query: Query<
-        (Entity, &Collider, &GlobalTransform, Option<&NavMeshAreaType>),
-        With<NavMeshAffector>,
+        (Entity, &Collider, &GlobalTransform, Option<&NavMeshAreaType>, Option<&NavMeshAffectorOverrides>),
+        (With<NavMeshAffector>, Without<NavMeshAffectorDisabled>),
     >,
+    #[cfg(feature = "debug_draw")] debug_stage: Res<debug_draw::NavMeshDebugStage>,
+    #[cfg(feature = "debug_draw")] debug_artifacts: Res<debug_draw::NavMeshDebugArtifacts>,
 ) {
-    let thread_pool = AsyncComputeTaskPool::get();
-    
-    let max_task_count = nav_mesh_settings.max_tile_generation_tasks.unwrap_or(u16::MAX) as usize - active_generation_tasks.0.len();
-    tiles_to_generate.extend(dirty_tiles.0.iter().take(max_task_count));
+    let thread_pool = task_pool.get();
+
+    let incremental_voxelization = nav_mesh_settings.experimental_incremental_voxelization;
+    if nav_mesh_settings.is_changed() {
+        // Cached tiles were voxelized with the previous settings.
+        if let Ok(mut cache) = voxelized_tile_cache.0.lock() {
+            cache.clear();
+        }
+        last_tile_generations.clear();
+    }
+
+    let mut max_task_count = nav_mesh_settings.max_tile_generation_tasks.unwrap_or(u16::MAX) as usize - active_generation_tasks.0.len();
+
+    let batch_size = nav_mesh_settings.tile_batch_size.unwrap_or(1);
+    if batch_size > 1 {
+        let can_batch_tile = |tile_coord: &UVec2| {
+            #[cfg(feature = "debug_draw")]
+            if debug_stage.tiles.contains(tile_coord) {
+                return false;
+            }
+
+            tile_affectors.get(tile_coord).is_some_and(|affectors| !affectors.is_empty())
+        };
+
+        for (block_min, block_size) in take_tile_batches(&mut dirty_tiles, batch_size, max_task_count, can_batch_tile) {
+            let mut tiles = Vec::with_capacity((block_size.x * block_size.y) as usize);
+            let mut affectors = HashSet::default();
+            for y in block_min.y..block_min.y + block_size.y {
+                for x in block_min.x..block_min.x + block_size.x {
+                    let tile_coord = UVec2::new(x, y);
+                    generation_ticker.0 += 1;
+
+                    // The whole block is re-voxelized, any cached tile is outdated once this task has been spawned.
+                    if incremental_voxelization {
+                        last_tile_generations.insert(tile_coord, generation_ticker.0);
+                        if let Ok(mut cache) = voxelized_tile_cache.0.lock() {
+                            cache.remove(&tile_coord);
+                        }
+                    }
+
+                    tiles.push((tile_coord, generation_ticker.0));
+                    if let Some(tile_affectors) = tile_affectors.get(&tile_coord) {
+                        affectors.extend(tile_affectors.iter().copied());
+                    }
+                }
+            }
+
+            let (geometry_collections, heightfield_collections) = gather_geometry(&affectors, &collider_query, &mut heightfields, &nav_mesh_settings);
+            let tile_coords = tiles.iter().map(|(tile_coord, _)| *tile_coord).collect();
+
+            let task = thread_pool.spawn(build_tile_batch(
+                tiles,
+                block_min,
+                block_size,
+                nav_mesh_settings.clone(),
+                geometry_collections,
+                heightfield_collections,
+                incremental_voxelization.then(|| voxelized_tile_cache.clone()),
+                nav_mesh.0.clone(),
+            ));
+
+            active_generation_tasks.0.push((task, tile_coords));
+            max_task_count -= 1;
+        }
+    }
+
+    tiles_to_generate.extend(dirty_tiles.0.iter().take(max_task_count).map(|(tile_coord, area)| (*tile_coord, *area)));
     
-    for tile_coord in tiles_to_generate.drain(..) {
+    for (tile_coord, dirty_area) in tiles_to_generate.drain(..) {
         dirty_tiles.0.remove(&tile_coord);
 
         generation_ticker.0 += 1;
 
-        let Some(affectors) = tile_affectors.get(&tile_coord) else {
+        // The cached tile can only be reused if it's from the last task spawned for this tile, otherwise a task that's still running could have different geometry.
+        let previous_voxelized_tile = if incremental_voxelization {
+            let last_generation = last_tile_generations.insert(tile_coord, generation_ticker.0);
+
+            let cached = voxelized_tile_cache.0.lock().ok().and_then(|mut cache| cache.remove(&tile_coord));
+
+            cached
+                .filter(|(generation, _)| Some(*generation) == last_generation)
+                .map(|(_, voxelized_tile)| (voxelized_tile, nav_mesh_settings.get_cells_in_area(tile_coord, dirty_area)))
+        } else {
+            None
+        };
+
+        let Some(affectors) = tile_affectors.get(&tile_coord).filter(|affectors| !affectors.is_empty()) else {
             // Spawn task to remove tile.
             thread_pool.spawn(remove_tile(generation_ticker.0, tile_coord, nav_mesh.0.clone())).detach();
             continue;
         };
-        if affectors.is_empty() {
-            // Spawn task to remove tile.
-            thread_pool
-                .spawn(remove_tile(
-                    generation_ticker.0,
-                    tile_coord,
-                    nav_mesh.0.clone(),
-                ))
-                .detach();
-            continue;
-        }
 
         // Step 1: Gather data.
-        let mut geometry_collections = Vec::with_capacity(affectors.len());
-        // Storing heightfields separately because they are massive.
-        let mut heightfield_collections = Vec::new();
-
-        let mut collider_iter = collider_query.iter_many(affectors.iter());
-        while let Some((entity, collider, global_transform, nav_mesh_affector)) = collider_iter.fetch_next() {
-            let area = nav_mesh_affector.map_or(Some(0), |area_type| area_type.0);
-
-            let type_to_convert = match collider.as_typed_shape() {
-                ColliderView::Ball(ball) => GeometryToConvert::Collider(ColliderType::Ball(*ball.raw)),
-                ColliderView::Cuboid(cuboid) => GeometryToConvert::Collider(ColliderType::Cuboid(*cuboid.raw)),
-                ColliderView::Capsule(capsule) => GeometryToConvert::Collider(ColliderType::Capsule(*capsule.raw)),
-                ColliderView::TriMesh(trimesh) => GeometryToConvert::RapierTriMesh(trimesh.raw.vertices().to_vec(), trimesh.indices().to_vec()),
-                ColliderView::HeightField(heightfield) => {
-                    // Deduplicate heightfields.
-                    let heightfield = if let Some(heightfield) = heightfields.get(&entity) {
-                        heightfield.clone()
-                    } else {
-                        let heightfield = Arc::new(heightfield.raw.clone());
-
-                        heightfields.insert(entity, heightfield.clone());
-
-                        heightfield
-                    };
-
-                    heightfield_collections.push(HeightFieldCollection {
-                        transform: global_transform.compute_transform(),
-                        heightfield,
-                        area,
-                    });
-
-                    continue;
-                },
-                ColliderView::ConvexPolyhedron(polyhedron) => {
-                    let tri = polyhedron.raw.to_trimesh();
-
-                    GeometryToConvert::RapierTriMesh(tri.0, tri.1)
-                },
-                ColliderView::Cylinder(cylinder) => GeometryToConvert::Collider(ColliderType::Cylinder(*cylinder.raw)),
-                ColliderView::Cone(cone) => GeometryToConvert::Collider(ColliderType::Cone(*cone.raw)),
-                ColliderView::RoundCuboid(round_cuboid) => GeometryToConvert::Collider(ColliderType::Cuboid(round_cuboid.raw.inner_shape)),
-                ColliderView::RoundCylinder(round_cylinder) => GeometryToConvert::Collider(ColliderType::Cylinder(round_cylinder.raw.inner_shape)),
-                ColliderView::RoundCone(round_cone) => GeometryToConvert::Collider(ColliderType::Cone(round_cone.raw.inner_shape)),
-                ColliderView::RoundConvexPolyhedron(round_polyhedron) => {
-                    let tri = round_polyhedron.inner_shape().raw.to_trimesh();
-
-                    GeometryToConvert::RapierTriMesh(tri.0, tri.1)
-                }
-                ColliderView::Triangle(triangle) => GeometryToConvert::Collider(ColliderType::Triangle(*triangle.raw)),
-                ColliderView::RoundTriangle(triangle) => {
-                    let inner_shape = triangle.inner_shape();
-
-                    GeometryToConvert::Collider(ColliderType::Triangle(*inner_shape.raw))
-                }
-                // TODO: This one requires me to think.
-                ColliderView::Compound(_) => {
-                    warn!("Compound colliders are not yet supported for nav-mesh generation, skipping for now..");
-                    continue;
-                }
-                // These ones do not make sense in this.
-                ColliderView::HalfSpace(_) => continue, /* This is like an infinite plane? We don't care. */
-                ColliderView::Polyline(_) => continue,  /* This is a line. */
-                ColliderView::Segment(_) => continue,   /* This is a line segment. */
-            };
-
-            geometry_collections.push(GeometryCollection {
-                transform: global_transform.compute_transform(),
-                geometry_to_convert: type_to_convert,
-                area,
-            });
-        }
+        let (geometry_collections, heightfield_collections) = gather_geometry(affectors, &collider_query, &mut heightfields, &nav_mesh_settings);
 
         // Step 2: Acquire nav_mesh lock
         let nav_mesh = nav_mesh.0.clone();
@@ -492,18 +944,222 @@ fn send_tile_rebuild_tasks_system(
             nav_mesh_settings.clone(),
             geometry_collections,
             heightfield_collections,
+            previous_voxelized_tile,
+            incremental_voxelization.then(|| voxelized_tile_cache.clone()),
+            #[cfg(feature = "debug_draw")]
+            debug_stage.tiles.contains(&tile_coord).then(|| debug_artifacts.clone()),
             nav_mesh,
         ));
 
-        active_generation_tasks.0.push(task);
+        active_generation_tasks.0.push((task, vec![tile_coord]));
     }
     heightfields.clear();
 }
 
+/// Removes up to ``max_batches`` blocks of adjacent dirty tiles from ``dirty_tiles``, returning the minimum tile & size in tiles of each block.
+///
+/// Blocks are at most ``batch_size`` tiles wide & deep and only contain tiles ``can_batch_tile`` returns true for. Single tiles are left in ``dirty_tiles``.
+fn take_tile_batches(
+    dirty_tiles: &mut DirtyTiles,
+    batch_size: u16,
+    max_batches: usize,
+    can_batch_tile: impl Fn(&UVec2) -> bool,
+) -> Vec<(UVec2, UVec2)> {
+    let batch_size = u32::from(batch_size);
+    let is_batchable = |dirty_tiles: &DirtyTiles, tile_coord: UVec2| {
+        dirty_tiles.0.contains_key(&tile_coord) && can_batch_tile(&tile_coord)
+    };
+
+    // Growing blocks from the lowest coordinate first means a block starts at the corner of a dirty area.
+    let mut seeds: Vec<UVec2> = dirty_tiles.0.keys().copied().collect();
+    seeds.sort_unstable_by_key(|tile_coord| (tile_coord.y, tile_coord.x));
+
+    let mut batches = Vec::new();
+    for seed in seeds {
+        if batches.len() >= max_batches {
+            break;
+        }
+        if !is_batchable(dirty_tiles, seed) {
+            continue;
+        }
+
+        let mut width = 1;
+        while width < batch_size
+            && seed.x.checked_add(width).is_some_and(|x| is_batchable(dirty_tiles, UVec2::new(x, seed.y)))
+        {
+            width += 1;
+        }
+
+        let mut depth = 1;
+        while depth < batch_size
+            && seed.y.checked_add(depth).is_some_and(|y| {
+                (seed.x..seed.x + width).all(|x| is_batchable(dirty_tiles, UVec2::new(x, y)))
+            })
+        {
+            depth += 1;
+        }
+
+        if width * depth == 1 {
+            continue;
+        }
+
+        for y in seed.y..seed.y + depth {
+            for x in seed.x..seed.x + width {
+                dirty_tiles.0.remove(&UVec2::new(x, y));
+            }
+        }
+
+        batches.push((seed, UVec2::new(width, depth)));
+    }
+
+    batches
+}
+
+/// Collects the geometry of ``affectors`` to convert & voxelize, ordered by entity. Heightfields are shared through ``heightfields`` instead of being cloned for every tile.
+#[allow(clippy::type_complexity)]
+fn gather_geometry<'a>(
+    affectors: impl IntoIterator<Item = &'a Entity>,
+    collider_query: &Query<
+        (Entity, &Collider, &GlobalTransform, Option<&NavMeshAreaType>, Option<&NavMeshAffectorOverrides>),
+        (With<NavMeshAffector>, Without<NavMeshAffectorDisabled>),
+    >,
+    heightfields: &mut HashMap<Entity, Arc<HeightField>>,
+    nav_mesh_settings: &NavMeshSettings,
+) -> (Vec<GeometryCollection>, Vec<HeightFieldCollection>) {
+    let mut geometry_collections = Vec::new();
+    // Storing heightfields separately because they are massive.
+    let mut heightfield_collections = Vec::new();
+
+    // Span merging depends on the order triangles are rasterized in, sort so a tile gets the same spans whichever set of affectors it's voxelized from.
+    let mut affectors: Vec<_> = affectors.into_iter().copied().collect();
+    affectors.sort_unstable();
+
+    let mut collider_iter = collider_query.iter_many(&affectors);
+    while let Some((entity, collider, global_transform, nav_mesh_affector, overrides)) = collider_iter.fetch_next() {
+        let area = nav_mesh_affector.map_or(Some(0), |area_type| area_type.0);
+        let (max_traversable_slope_radians, step_height) = get_affector_walkable_settings(overrides, nav_mesh_settings);
+
+        if let ColliderView::HeightField(heightfield) = collider.as_unscaled_typed_shape() {
+            // Deduplicate heightfields.
+            let heightfield = if let Some(heightfield) = heightfields.get(&entity) {
+                heightfield.clone()
+            } else {
+                let heightfield = Arc::new(heightfield.raw.clone());
+
+                heightfields.insert(entity, heightfield.clone());
+
+                heightfield
+            };
+
+            heightfield_collections.push(HeightFieldCollection {
+                transform: get_nav_mesh_space_transform(global_transform, nav_mesh_settings),
+                heightfield,
+                area,
+                max_traversable_slope_radians,
+                step_height,
+            });
+
+            continue;
+        }
+
+        let Some(type_to_convert) = get_geometry_to_convert(collider) else {
+            continue;
+        };
+
+        geometry_collections.push(GeometryCollection {
+            transform: get_nav_mesh_space_transform(global_transform, nav_mesh_settings),
+            geometry_to_convert: type_to_convert,
+            area,
+            max_traversable_slope_radians,
+            step_height,
+        });
+    }
+
+    (geometry_collections, heightfield_collections)
+}
+
+/// Recovers the nav-mesh if the lock has been poisoned & marks every tile with affectors as dirty to regenerate it.
+fn recover_poisoned_nav_mesh_system(
+    nav_mesh: Res<NavMesh>,
+    nav_mesh_settings: Res<NavMeshSettings>,
+    tile_affectors: Res<TileAffectors>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+) {
+    if !nav_mesh.is_poisoned() {
+        return;
+    }
+
+    warn!("Nav-Mesh lock has been poisoned. Resetting the nav-mesh & regenerating all tiles.");
+    nav_mesh.recover();
+
+    for (tile_coord, _) in tile_affectors.iter().filter(|(_, affectors)| !affectors.is_empty()) {
+        dirty_tiles.mark_tile(*tile_coord, &nav_mesh_settings);
+    }
+}
+
+/// Returns the max traversable slope & step height to use for an affector's geometry.
+fn get_affector_walkable_settings(
+    overrides: Option<&NavMeshAffectorOverrides>,
+    nav_mesh_settings: &NavMeshSettings,
+) -> (f32, u16) {
+    let overrides = overrides.copied().unwrap_or_default();
+
+    (
+        overrides
+            .max_traversable_slope_radians
+            .unwrap_or(nav_mesh_settings.max_traversable_slope_radians),
+        overrides.step_height.unwrap_or(nav_mesh_settings.step_height),
+    )
+}
+
+/// Returns the geometry to convert for a collider or ``None`` if it isn't supported or is a heightfield.
+fn get_geometry_to_convert(collider: &Collider) -> Option<GeometryToConvert> {
+    // The unscaled shape is used as the scale is applied from the transform during conversion.
+    let geometry = match collider.as_unscaled_typed_shape() {
+        ColliderView::Ball(ball) => GeometryToConvert::Collider(ColliderType::Ball(*ball.raw)),
+        ColliderView::Cuboid(cuboid) => GeometryToConvert::Collider(ColliderType::Cuboid(*cuboid.raw)),
+        ColliderView::Capsule(capsule) => GeometryToConvert::Collider(ColliderType::Capsule(*capsule.raw)),
+        ColliderView::TriMesh(trimesh) => GeometryToConvert::RapierTriMesh(trimesh.raw.vertices().to_vec(), trimesh.indices().to_vec()),
+        ColliderView::HeightField(_) => return None, // Heightfields are handled separately as they are shared between tiles.
+        ColliderView::ConvexPolyhedron(polyhedron) => {
+            let tri = polyhedron.raw.to_trimesh();
+
+            GeometryToConvert::RapierTriMesh(tri.0, tri.1)
+        },
+        ColliderView::Cylinder(cylinder) => GeometryToConvert::Collider(ColliderType::Cylinder(*cylinder.raw)),
+        ColliderView::Cone(cone) => GeometryToConvert::Collider(ColliderType::Cone(*cone.raw)),
+        ColliderView::RoundCuboid(round_cuboid) => GeometryToConvert::Collider(ColliderType::Cuboid(round_cuboid.raw.inner_shape)),
+        ColliderView::RoundCylinder(round_cylinder) => GeometryToConvert::Collider(ColliderType::Cylinder(round_cylinder.raw.inner_shape)),
+        ColliderView::RoundCone(round_cone) => GeometryToConvert::Collider(ColliderType::Cone(round_cone.raw.inner_shape)),
+        ColliderView::RoundConvexPolyhedron(round_polyhedron) => {
+            let tri = round_polyhedron.inner_shape().raw.to_trimesh();
+
+            GeometryToConvert::RapierTriMesh(tri.0, tri.1)
+        }
+        ColliderView::Triangle(triangle) => GeometryToConvert::Collider(ColliderType::Triangle(*triangle.raw)),
+        ColliderView::RoundTriangle(triangle) => {
+            let inner_shape = triangle.inner_shape();
+
+            GeometryToConvert::Collider(ColliderType::Triangle(*inner_shape.raw))
+        }
+        // TODO: This one requires me to think.
+        ColliderView::Compound(_) => {
+            warn!("Compound colliders are not yet supported for nav-mesh generation, skipping for now..");
+            return None;
+        }
+        // These ones do not make sense in this.
+        ColliderView::HalfSpace(_) => return None, /* This is like an infinite plane? We don't care. */
+        ColliderView::Polyline(_) => return None,  /* This is a line. */
+        ColliderView::Segment(_) => return None,   /* This is a line segment. */
+    };
+
+    Some(geometry)
+}
+
 fn remove_finished_tasks(
     mut active_generation_tasks: ResMut<ActiveGenerationTasks> 
 ) {
-    active_generation_tasks.0.retain(|task| !task.is_finished());
+    active_generation_tasks.0.retain(|(task, _)| !task.is_finished());
 }
 
 async fn remove_tile(
@@ -512,7 +1168,7 @@ async fn remove_tile(
     nav_mesh: Arc<RwLock<NavMeshTiles>>,
 ) {
     let Ok(mut nav_mesh) = nav_mesh.write() else {
-        error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
+        warn!("Nav-Mesh lock has been poisoned. The tile will be regenerated once the nav-mesh has been recovered.");
         return;
     };
 
@@ -521,46 +1177,382 @@ async fn remove_tile(
         nav_mesh.remove_tile(tile_coord);
     }
 }
+#[allow(clippy::too_many_arguments)]
 async fn build_tile(
     generation: u64,
     tile_coord: UVec2,
     nav_mesh_settings: NavMeshSettings,
     geometry_collections: Vec<GeometryCollection>,
     heightfields: Vec<HeightFieldCollection>,
+    previous_voxelized_tile: Option<(VoxelizedTile, CellRange)>,
+    voxelized_tile_cache: Option<VoxelizedTileCache>,
+    #[cfg(feature = "debug_draw")] debug_artifacts: Option<debug_draw::NavMeshDebugArtifacts>,
     nav_mesh: Arc<RwLock<NavMeshTiles>>,
 ) {
-    let triangle_collection = convert_geometry_collections(geometry_collections);
+    #[cfg(feature = "debug_draw")]
+    let mut tile_debug_artifacts = debug_artifacts.as_ref().map(|_| debug_draw::TileDebugArtifacts::default());
+
+    let nav_mesh_tile = build_nav_mesh_tile(
+        tile_coord,
+        &nav_mesh_settings,
+        || voxelize_tile(tile_coord, &nav_mesh_settings, geometry_collections, heightfields, previous_voxelized_tile),
+        #[cfg(feature = "debug_draw")]
+        tile_debug_artifacts.as_mut(),
+        |voxelized_tile| cache_voxelized_tile(voxelized_tile_cache.as_ref(), tile_coord, generation, voxelized_tile),
+    );
+
+    #[cfg(feature = "debug_draw")]
+    if let (Some(debug_artifacts), Some(tile_debug_artifacts)) = (debug_artifacts, tile_debug_artifacts) {
+        debug_artifacts.insert(tile_coord, tile_debug_artifacts);
+    }
 
-    let voxelized_tile =
-        build_heightfield_tile(tile_coord, triangle_collection, heightfields, &nav_mesh_settings);
+    let Ok(mut nav_mesh) = nav_mesh.write() else {
+        warn!("Nav-Mesh lock has been poisoned. The tile will be regenerated once the nav-mesh has been recovered.");
+        return;
+    };
 
-    let mut open_tile = build_open_heightfield_tile(voxelized_tile, &nav_mesh_settings);
+    if nav_mesh.tile_generations.get(&tile_coord).unwrap_or(&0) < &generation {
+        nav_mesh.tile_generations.insert(tile_coord, generation);
 
-    // Remove areas that are too close to a wall.
-    erode_walkable_area(&mut open_tile, &nav_mesh_settings);
+        nav_mesh.add_tile(tile_coord, nav_mesh_tile);
+    }
+}
 
-    calculate_distance_field(&mut open_tile, &nav_mesh_settings);
-    build_regions(&mut open_tile, &nav_mesh_settings);
+/// Generates a block of adjacent tiles in a single task, voxelizing the block's geometry once.
+///
+/// ``tiles`` contains the coordinate & generation of every tile in the block, ordered row by row like [build_heightfield_block] returns them.
+#[allow(clippy::too_many_arguments)]
+async fn build_tile_batch(
+    tiles: Vec<(UVec2, u64)>,
+    block_min: UVec2,
+    block_size: UVec2,
+    nav_mesh_settings: NavMeshSettings,
+    geometry_collections: Vec<GeometryCollection>,
+    heightfields: Vec<HeightFieldCollection>,
+    voxelized_tile_cache: Option<VoxelizedTileCache>,
+    nav_mesh: Arc<RwLock<NavMeshTiles>>,
+) {
+    let voxelized_tiles = {
+        let triangle_collections = convert_geometry_collections(geometry_collections);
 
-    let contour_set = build_contours(open_tile, &nav_mesh_settings);
+        #[cfg(feature = "trace")]
+        let _span = info_span!("build_heightfield_block", block_min = %block_min, block_size = %block_size).entered();
 
-    let poly_mesh = build_poly_mesh(contour_set, &nav_mesh_settings);
+        build_heightfield_block(block_min, block_size, triangle_collections, heightfields, &nav_mesh_settings)
+    };
 
-    let nav_mesh_tile =
-        create_nav_mesh_tile_from_poly_mesh(poly_mesh, tile_coord, &nav_mesh_settings);
+    let nav_mesh_tiles: Vec<_> = tiles
+        .into_iter()
+        .zip(voxelized_tiles)
+        .map(|((tile_coord, generation), voxelized_tile)| {
+            let nav_mesh_tile = build_nav_mesh_tile(
+                tile_coord,
+                &nav_mesh_settings,
+                || voxelized_tile,
+                #[cfg(feature = "debug_draw")]
+                None,
+                |voxelized_tile| cache_voxelized_tile(voxelized_tile_cache.as_ref(), tile_coord, generation, voxelized_tile),
+            );
+
+            (tile_coord, generation, nav_mesh_tile)
+        })
+        .collect();
 
     let Ok(mut nav_mesh) = nav_mesh.write() else {
-        error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
+        warn!("Nav-Mesh lock has been poisoned. The tiles will be regenerated once the nav-mesh has been recovered.");
         return;
     };
 
-    if nav_mesh.tile_generations.get(&tile_coord).unwrap_or(&0) < &generation {
-        nav_mesh.tile_generations.insert(tile_coord, generation);
+    for (tile_coord, generation, nav_mesh_tile) in nav_mesh_tiles {
+        if nav_mesh.tile_generations.get(&tile_coord).unwrap_or(&0) < &generation {
+            nav_mesh.tile_generations.insert(tile_coord, generation);
 
-        nav_mesh.add_tile(tile_coord, nav_mesh_tile, &nav_mesh_settings);
+            nav_mesh.add_tile(tile_coord, nav_mesh_tile);
+        }
     }
 }
 
+/// Stores ``voxelized_tile`` in the cache if there is one, unless a newer task already stored the tile.
+#[allow(clippy::unnecessary_map_or)]
+fn cache_voxelized_tile(
+    voxelized_tile_cache: Option<&VoxelizedTileCache>,
+    tile_coord: UVec2,
+    generation: u64,
+    voxelized_tile: VoxelizedTile,
+) {
+    let Some(Ok(mut cache)) = voxelized_tile_cache.map(|cache| cache.0.lock()) else {
+        return;
+    };
+
+    // Don't replace a tile from a newer task that finished before us.
+    if cache.get(&tile_coord).map_or(true, |(cached_generation, _)| *cached_generation < generation) {
+        cache.insert(tile_coord, (generation, voxelized_tile));
+    }
+}
+
+/// Voxelizes the geometry of a single tile, used with [build_nav_mesh_tile].
+fn voxelize_tile(
+    tile_coord: UVec2,
+    nav_mesh_settings: &NavMeshSettings,
+    geometry_collections: Vec<GeometryCollection>,
+    heightfields: Vec<HeightFieldCollection>,
+    previous_voxelized_tile: Option<(VoxelizedTile, CellRange)>,
+) -> VoxelizedTile {
+    let triangle_collection = convert_geometry_collections(geometry_collections);
+
+    #[cfg(feature = "trace")]
+    let _span = info_span!("build_heightfield_tile", tile = %tile_coord).entered();
+
+    build_heightfield_tile(
+        tile_coord,
+        triangle_collection,
+        heightfields,
+        nav_mesh_settings,
+        previous_voxelized_tile,
+    )
+}
+
+/// Runs the generation pipeline for a single tile. Shared between [build_tile], [build_tile_batch] & [generate_navmesh].
+///
+/// ``voxelize`` returns the voxelized tile, either from [voxelize_tile] or a block voxelized by [build_heightfield_block].
+/// ``on_voxelized`` receives the voxelized tile once it's no longer needed.
+fn build_nav_mesh_tile(
+    tile_coord: UVec2,
+    nav_mesh_settings: &NavMeshSettings,
+    voxelize: impl FnOnce() -> VoxelizedTile,
+    #[cfg(feature = "debug_draw")] mut debug_artifacts: Option<&mut debug_draw::TileDebugArtifacts>,
+    on_voxelized: impl FnOnce(VoxelizedTile),
+) -> NavMeshTile {
+    #[cfg(feature = "trace")]
+    let tile_span = info_span!("build_tile", tile = %tile_coord, polygons = field::Empty).entered();
+
+    let voxelized_tile = voxelize();
+
+    #[cfg(feature = "debug_draw")]
+    if let Some(debug_artifacts) = debug_artifacts.as_mut() {
+        debug_artifacts.capture_voxelized_tile(tile_coord, &voxelized_tile, nav_mesh_settings);
+    }
+
+    let mut open_tile = {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("build_open_heightfield_tile", tile = %tile_coord).entered();
+
+        build_open_heightfield_tile(&voxelized_tile, nav_mesh_settings)
+    };
+    on_voxelized(voxelized_tile);
+
+    // Tiles that merely overlap geometry with their border are expected to be empty, only tiles with walkable spans of their own are worth a warning.
+    let had_walkable_spans = has_walkable_spans(&open_tile, nav_mesh_settings);
+
+    // Measured before eroding, so walls are where the geometry is.
+    let clearance_field = {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("calculate_clearance_field", tile = %tile_coord).entered();
+
+        calculate_clearance_field(&mut open_tile, nav_mesh_settings)
+    };
+
+    // Remove areas that are too close to a wall.
+    {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("erode_walkable_area", tile = %tile_coord).entered();
+
+        erode_walkable_area(&mut open_tile, nav_mesh_settings);
+    }
+    let eroded_all_spans = had_walkable_spans && !has_walkable_spans(&open_tile, nav_mesh_settings);
+
+    {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("calculate_distance_field", tile = %tile_coord).entered();
+
+        calculate_distance_field(&mut open_tile, nav_mesh_settings);
+    }
+
+    #[cfg(feature = "debug_draw")]
+    if let Some(debug_artifacts) = debug_artifacts.as_mut() {
+        debug_artifacts.capture_distance_field(tile_coord, &open_tile, nav_mesh_settings);
+    }
+
+    {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("build_regions", tile = %tile_coord).entered();
+
+        build_regions(&mut open_tile, nav_mesh_settings);
+    }
+    let removed_all_regions = had_walkable_spans
+        && !eroded_all_spans
+        && !open_tile.any_span_in_tile(nav_mesh_settings, |span| span.region != 0);
+
+    #[cfg(feature = "debug_draw")]
+    if let Some(debug_artifacts) = debug_artifacts {
+        debug_artifacts.capture_regions(&open_tile);
+        debug_artifacts.capture_raw_contours(tile_coord, &open_tile, nav_mesh_settings);
+    }
+
+    let contour_set = {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("build_contours", tile = %tile_coord).entered();
+
+        build_contours(open_tile, nav_mesh_settings)
+    };
+
+    let poly_mesh = {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("build_poly_mesh", tile = %tile_coord).entered();
+
+        build_poly_mesh(contour_set, nav_mesh_settings)
+    };
+
+    let nav_mesh_tile = create_nav_mesh_tile_from_poly_mesh(poly_mesh, &clearance_field, tile_coord, nav_mesh_settings);
+
+    #[cfg(feature = "trace")]
+    tile_span.record("polygons", nav_mesh_tile.polygons.len());
+
+    if had_walkable_spans && nav_mesh_tile.polygons.is_empty() {
+        let hint = if eroded_all_spans {
+            "All walkable cells were eroded, walkable_radius is likely too large for the geometry."
+        } else if removed_all_regions {
+            "All regions were filtered out, min_region_area is likely too large."
+        } else {
+            "No polygons could be built from the contours, try lowering max_contour_simplification_error."
+        };
+
+        warn_empty_tile(tile_coord, hint);
+    }
+
+    nav_mesh_tile
+}
+
+fn has_walkable_spans(open_tile: &OpenTile, nav_mesh_settings: &NavMeshSettings) -> bool {
+    open_tile.any_span_in_tile(nav_mesh_settings, |span| {
+        open_tile.areas[span.tile_index].is_some()
+    })
+}
+
+/// Minimum time between warnings about tiles that generated no polygons. A bad setting usually empties many tiles at once.
+const EMPTY_TILE_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
+fn warn_empty_tile(tile_coord: UVec2, hint: &str) {
+    // (Last warning, warnings suppressed since.) Shared between all generation tasks.
+    static EMPTY_TILE_WARNINGS: Mutex<(Option<Instant>, u32)> = Mutex::new((None, 0));
+
+    let mut warnings = EMPTY_TILE_WARNINGS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let (last_warning, suppressed) = &mut *warnings;
+
+    let now = Instant::now();
+    if last_warning.is_some_and(|last_warning| {
+        now.duration_since(last_warning) < EMPTY_TILE_WARNING_INTERVAL
+    }) {
+        *suppressed += 1;
+        return;
+    }
+
+    if *suppressed > 0 {
+        warn!(
+            "Tile {} has walkable geometry but generated no polygons. {} ({} similar warnings suppressed.)",
+            tile_coord, hint, suppressed
+        );
+    } else {
+        warn!(
+            "Tile {} has walkable geometry but generated no polygons. {}",
+            tile_coord, hint
+        );
+    }
+
+    *last_warning = Some(now);
+    *suppressed = 0;
+}
+
+/// Generates a nav-mesh from ``colliders`` synchronously on the calling thread, no [App] or [OxidizedNavigationPlugin] required.
+///
+/// Every tile touched by a collider is generated & connected to its neighbours. Useful for baking nav-meshes ahead of time & for tests.
+///
+/// * ``nav_mesh_settings`` - Settings to generate the nav-mesh with.
+/// * ``colliders`` - Same data as an entity with a [NavMeshAffector]: the collider, its transform, an optional area type & optional overrides.
+pub fn generate_navmesh<'a>(
+    nav_mesh_settings: &NavMeshSettings,
+    colliders: impl IntoIterator<
+        Item = (
+            &'a Collider,
+            &'a GlobalTransform,
+            Option<&'a NavMeshAreaType>,
+            Option<&'a NavMeshAffectorOverrides>,
+        ),
+    >,
+) -> NavMeshTiles {
+    let colliders: Vec<_> = colliders.into_iter().collect();
+
+    let mut tile_affectors: HashMap<UVec2, Vec<usize>> = HashMap::default();
+    for (i, (collider, global_transform, _, _)) in colliders.iter().enumerate() {
+        let (_, min_tile, max_tile) = get_affector_tile_coverage(collider, global_transform, nav_mesh_settings);
+
+        for x in min_tile.x..=max_tile.x {
+            for y in min_tile.y..=max_tile.y {
+                tile_affectors.entry(UVec2::new(x, y)).or_default().push(i);
+            }
+        }
+    }
+
+    // Converted once as heightfields are shared between tiles.
+    let heightfields: Vec<_> = colliders
+        .iter()
+        .map(|(collider, _, _, _)| match collider.as_unscaled_typed_shape() {
+            ColliderView::HeightField(heightfield) => Some(Arc::new(heightfield.raw.clone())),
+            _ => None,
+        })
+        .collect();
+
+    let mut nav_mesh = NavMeshTiles::default();
+    for (tile_coord, affectors) in tile_affectors {
+        let mut geometry_collections = Vec::with_capacity(affectors.len());
+        let mut heightfield_collections = Vec::new();
+
+        for i in affectors {
+            let (collider, global_transform, area_type, overrides) = colliders[i];
+            let area = area_type.map_or(Some(0), |area_type| area_type.0);
+            let (max_traversable_slope_radians, step_height) = get_affector_walkable_settings(overrides, nav_mesh_settings);
+
+            if let Some(heightfield) = &heightfields[i] {
+                heightfield_collections.push(HeightFieldCollection {
+                    transform: get_nav_mesh_space_transform(global_transform, nav_mesh_settings),
+                    heightfield: heightfield.clone(),
+                    area,
+                    max_traversable_slope_radians,
+                    step_height,
+                });
+                continue;
+            }
+
+            let Some(geometry_to_convert) = get_geometry_to_convert(collider) else {
+                continue;
+            };
+
+            geometry_collections.push(GeometryCollection {
+                transform: get_nav_mesh_space_transform(global_transform, nav_mesh_settings),
+                geometry_to_convert,
+                area,
+                max_traversable_slope_radians,
+                step_height,
+            });
+        }
+
+        let nav_mesh_tile = build_nav_mesh_tile(
+            tile_coord,
+            nav_mesh_settings,
+            || voxelize_tile(tile_coord, nav_mesh_settings, geometry_collections, heightfield_collections, None),
+            #[cfg(feature = "debug_draw")]
+            None,
+            |_| {},
+        );
+
+        nav_mesh.add_tile(tile_coord, nav_mesh_tile);
+    }
+
+    nav_mesh
+}
+
 /*
 *   Lots of math stuff.
 *   Don't know where else to put it.
@@ -1,4 +1,5 @@
-use bevy::prelude::{info, UVec2, UVec3, UVec4};
+use bevy::prelude::{info, UVec2, UVec3, UVec4, Vec3};
+use smallvec::SmallVec;
 
 use crate::contour::ContourSet;
 
@@ -7,13 +8,15 @@ use super::{intersect, intersect_prop, left, left_on, NavMeshSettings};
 #[derive(Default)]
 pub struct PolyMesh {
     pub vertices: Vec<UVec3>,
-    pub polygons: Vec<[u32; VERTICES_IN_TRIANGLE]>, //
-    pub edges: Vec<[EdgeConnection; VERTICES_IN_TRIANGLE]>, // For each polygon edge points to a polygon (if any) that shares the edge.
+    pub polygons: Vec<SmallVec<[u32; MAX_VERTICES_PER_POLYGON]>>, // Convex, triangles unless polygons have been merged.
+    pub edges: Vec<SmallVec<[EdgeConnection; MAX_VERTICES_PER_POLYGON]>>, // For each polygon edge points to a polygon (if any) that shares the edge.
     pub areas: Vec<u16>,
 }
 
 const VERTEX_BUCKET_COUNT: usize = 1 << 12; // 4 096
-pub const VERTICES_IN_TRIANGLE: usize = 3; // Don't change this. The mesher can't make anything other than triangles.
+pub const VERTICES_IN_TRIANGLE: usize = 3; // Don't change this. Triangulation can't make anything other than triangles.
+/// Upper limit of [NavMeshSettings::max_vertices_per_polygon].
+pub const MAX_VERTICES_PER_POLYGON: usize = 6;
 
 pub fn build_poly_mesh(contour_set: ContourSet, nav_mesh_settings: &NavMeshSettings) -> PolyMesh {
     let mut max_vertices = 0;
@@ -42,6 +45,7 @@ pub fn build_poly_mesh(contour_set: ContourSet, nav_mesh_settings: &NavMeshSetti
 
     let mut indices = Vec::with_capacity(max_verts_per_contour);
     let mut triangles = Vec::with_capacity(max_verts_per_contour * 3);
+    let mut contour_polygons = Vec::with_capacity(max_verts_per_contour);
 
     for contour in &contour_set.contours {
         if contour.vertices.len() < 3 {
@@ -50,6 +54,7 @@ pub fn build_poly_mesh(contour_set: ContourSet, nav_mesh_settings: &NavMeshSetti
 
         indices.clear();
         triangles.clear();
+        contour_polygons.clear();
 
         indices.extend(0..contour.vertices.len() as u32);
 
@@ -74,15 +79,23 @@ pub fn build_poly_mesh(contour_set: ContourSet, nav_mesh_settings: &NavMeshSetti
             let c = triangles[i * 3 + 2];
 
             if a != b && a != c && b != c {
-                poly_mesh.polygons.push([
+                contour_polygons.push(SmallVec::from_slice(&[
                     indices[a as usize],
                     indices[b as usize],
                     indices[c as usize],
-                ]);
-
-                poly_mesh.areas.push(contour.area);
+                ]));
             }
         }
+
+        let max_vertices_per_polygon = nav_mesh_settings
+            .max_vertices_per_polygon
+            .clamp(VERTICES_IN_TRIANGLE, MAX_VERTICES_PER_POLYGON);
+        if max_vertices_per_polygon > VERTICES_IN_TRIANGLE {
+            merge_polygons(&mut contour_polygons, &poly_mesh.vertices, max_vertices_per_polygon, nav_mesh_settings);
+        }
+
+        poly_mesh.areas.resize(poly_mesh.areas.len() + contour_polygons.len(), contour.area);
+        poly_mesh.polygons.append(&mut contour_polygons);
     }
 
     // For each edge, find other polygon that shares that edge.
@@ -159,11 +172,11 @@ struct Edge {
 }
 
 fn build_mesh_adjacency(
-    polygons: &[[u32; VERTICES_IN_TRIANGLE]],
+    polygons: &[SmallVec<[u32; MAX_VERTICES_PER_POLYGON]>],
     vertex_count: usize,
-    in_edges: &mut Vec<[EdgeConnection; VERTICES_IN_TRIANGLE]>,
+    in_edges: &mut Vec<SmallVec<[EdgeConnection; MAX_VERTICES_PER_POLYGON]>>,
 ) {
-    let max_edge_count = polygons.len() * VERTICES_IN_TRIANGLE;
+    let max_edge_count = polygons.iter().map(|indices| indices.len()).sum();
 
     let mut first_edge = vec![None; vertex_count];
     let mut next_edge = vec![None; max_edge_count];
@@ -205,7 +218,11 @@ fn build_mesh_adjacency(
     }
 
     in_edges.clear();
-    in_edges.resize(polygons.len(), [EdgeConnection::None; VERTICES_IN_TRIANGLE]);
+    in_edges.extend(
+        polygons
+            .iter()
+            .map(|indices| SmallVec::from_elem(EdgeConnection::None, indices.len())),
+    );
     for edge in edges.iter() {
         if edge.polygon[0] != edge.polygon[1] {
             let polygon_one = edge.polygon[0];
@@ -218,6 +235,169 @@ fn build_mesh_adjacency(
     }
 }
 
+/// Polygons ``a`` & ``b`` (``a < b``) can be merged along ``edge_a`` & ``edge_b``, see [get_polygon_merge_value].
+struct MergeCandidate {
+    value: u32,
+    a: usize,
+    b: usize,
+    edge_a: usize,
+    edge_b: usize,
+}
+
+/// Greedily merges polygons sharing an edge, longest shared edge first, as long as the result is convex, planar & has at most ``max_vertices_per_polygon`` vertices.
+///
+/// All ``polygons`` must be from the same contour as they are assumed to have the same area.
+fn merge_polygons(
+    polygons: &mut Vec<SmallVec<[u32; MAX_VERTICES_PER_POLYGON]>>,
+    vertices: &[UVec3],
+    max_vertices_per_polygon: usize,
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    let get_merge_candidate = |polygons: &[Option<SmallVec<[u32; MAX_VERTICES_PER_POLYGON]>>], a: usize, b: usize| {
+        let (a, b) = (a.min(b), a.max(b));
+        let (Some(polygon_a), Some(polygon_b)) = (&polygons[a], &polygons[b]) else {
+            return None;
+        };
+
+        get_polygon_merge_value(polygon_a, polygon_b, vertices, max_vertices_per_polygon, nav_mesh_settings)
+            .map(|(value, edge_a, edge_b)| MergeCandidate { value, a, b, edge_a, edge_b })
+    };
+
+    // Merged away polygons are left as None so indices in the candidates stay valid.
+    let mut merged_polygons: Vec<_> = polygons.drain(..).map(Some).collect();
+
+    // Only candidates involving a merged polygon change after a merge, so the rest are kept instead of testing every pair again.
+    let mut candidates: Vec<MergeCandidate> = (0..merged_polygons.len())
+        .flat_map(|a| ((a + 1)..merged_polygons.len()).map(move |b| (a, b)))
+        .filter_map(|(a, b)| get_merge_candidate(&merged_polygons, a, b))
+        .collect();
+
+    // Ties go to the first pair of polygons.
+    while let Some(best) = candidates
+        .iter()
+        .enumerate()
+        .max_by(|(_, x), (_, y)| x.value.cmp(&y.value).then((y.a, y.b).cmp(&(x.a, x.b))))
+        .map(|(index, _)| index)
+    {
+        let MergeCandidate { a, b, edge_a, edge_b, .. } = candidates.swap_remove(best);
+
+        let (Some(polygon_a), Some(polygon_b)) = (merged_polygons[a].take(), merged_polygons[b].take()) else {
+            continue;
+        };
+        merged_polygons[a] = Some(merge_polygon_vertices(&polygon_a, &polygon_b, edge_a, edge_b));
+
+        candidates.retain(|candidate| ![a, b].contains(&candidate.a) && ![a, b].contains(&candidate.b));
+        candidates.extend(
+            (0..merged_polygons.len())
+                .filter(|other| *other != a)
+                .filter_map(|other| get_merge_candidate(&merged_polygons, a, other)),
+        );
+    }
+
+    polygons.extend(merged_polygons.into_iter().flatten());
+}
+
+/// Returns the squared length of the shared edge along with the index of the edge in each polygon if ``polygon_a`` & ``polygon_b`` can be merged.
+fn get_polygon_merge_value(
+    polygon_a: &[u32],
+    polygon_b: &[u32],
+    vertices: &[UVec3],
+    max_vertices_per_polygon: usize,
+    nav_mesh_settings: &NavMeshSettings,
+) -> Option<(u32, usize, usize)> {
+    let count_a = polygon_a.len();
+    let count_b = polygon_b.len();
+
+    if count_a + count_b - 2 > max_vertices_per_polygon {
+        return None;
+    }
+
+    // Find the shared edge. It's wound in opposite directions in the two polygons.
+    let (edge_a, edge_b) = (0..count_a).find_map(|i| {
+        let a_start = polygon_a[i];
+        let a_end = polygon_a[(i + 1) % count_a];
+
+        (0..count_b)
+            .find(|j| polygon_b[*j] == a_end && polygon_b[(j + 1) % count_b] == a_start)
+            .map(|j| (i, j))
+    })?;
+
+    // The merged polygon must stay convex at both ends of the shared edge.
+    let vertex = |index: u32| vertices[index as usize].extend(0).as_ivec4();
+
+    let previous = vertex(polygon_a[(edge_a + count_a - 1) % count_a]);
+    let current = vertex(polygon_a[edge_a]);
+    let next = vertex(polygon_b[(edge_b + 2) % count_b]);
+    if !left(previous, current, next) {
+        return None;
+    }
+
+    let previous = vertex(polygon_b[(edge_b + count_b - 1) % count_b]);
+    let current = vertex(polygon_b[edge_b]);
+    let next = vertex(polygon_a[(edge_a + 2) % count_a]);
+    if !left(previous, current, next) {
+        return None;
+    }
+
+    let merged = merge_polygon_vertices(polygon_a, polygon_b, edge_a, edge_b);
+    if !is_polygon_planar(&merged, vertices, nav_mesh_settings) {
+        return None;
+    }
+
+    let start = vertices[polygon_a[edge_a] as usize];
+    let end = vertices[polygon_a[(edge_a + 1) % count_a] as usize];
+    let delta_x = start.x.abs_diff(end.x);
+    let delta_z = start.z.abs_diff(end.z);
+
+    Some((delta_x * delta_x + delta_z * delta_z, edge_a, edge_b))
+}
+
+fn merge_polygon_vertices(
+    polygon_a: &[u32],
+    polygon_b: &[u32],
+    edge_a: usize,
+    edge_b: usize,
+) -> SmallVec<[u32; MAX_VERTICES_PER_POLYGON]> {
+    let count_a = polygon_a.len();
+    let count_b = polygon_b.len();
+
+    // Both polygons without the shared edge's start vertex, starting from the edge's end.
+    (0..count_a - 1)
+        .map(|i| polygon_a[(edge_a + 1 + i) % count_a])
+        .chain((0..count_b - 1).map(|i| polygon_b[(edge_b + 1 + i) % count_b]))
+        .collect()
+}
+
+/// Checks that every vertex of the polygon is within one ``cell_height`` of the polygon's plane.
+fn is_polygon_planar(polygon: &[u32], vertices: &[UVec3], nav_mesh_settings: &NavMeshSettings) -> bool {
+    let scale = Vec3::new(nav_mesh_settings.cell_width, nav_mesh_settings.cell_height, nav_mesh_settings.cell_width);
+    let points: SmallVec<[Vec3; MAX_VERTICES_PER_POLYGON]> = polygon
+        .iter()
+        .map(|index| vertices[*index as usize].as_vec3() * scale)
+        .collect();
+
+    // Newell's method, robust for slightly non-planar polygons.
+    let mut normal = Vec3::ZERO;
+    for (i, current) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+
+        normal.x += (current.y - next.y) * (current.z + next.z);
+        normal.y += (current.z - next.z) * (current.x + next.x);
+        normal.z += (current.x - next.x) * (current.y + next.y);
+    }
+    if normal.y.abs() <= f32::EPSILON {
+        return false;
+    }
+
+    let center = points.iter().copied().sum::<Vec3>() / points.len() as f32;
+
+    points.iter().all(|point| {
+        let vertical_distance = normal.dot(*point - center) / normal.y;
+
+        vertical_distance.abs() <= nav_mesh_settings.cell_height
+    })
+}
+
 fn compute_vertex_hash(x: u64, z: u64) -> u64 {
     // I am not sure if this is completely necessary.
     const HASH_X: u64 = 0x8da6b343; // Multipliers from Recast's version. "Large multiplicative constants"
@@ -1,9 +1,14 @@
 //! Module for querying the nav-mesh.
 
-use bevy::prelude::{UVec2, Vec3};
+use bevy::{
+    prelude::{BVec3, UVec2, Vec2, Vec3},
+    utils::{HashMap, HashSet},
+};
+use smallvec::SmallVec;
 
 use crate::{
-    tiles::{Link, NavMeshTiles},
+    mesher::VERTICES_IN_TRIANGLE,
+    tiles::{connect_to_neighbours, weighted_distance_squared, Link, NavMeshTile, NavMeshTiles, PolygonRef},
     NavMeshSettings,
 };
 
@@ -39,6 +44,35 @@ pub enum FindPolygonPathError {
     NoValidEndPolygon,
 }
 
+/// Optional parameters for pathfinding. Used with [find_polygon_path_with_options] & [find_path_with_options].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct FindPathOptions<'a> {
+    /// Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
+    pub position_search_radius: Option<f32>,
+    /// Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+    pub area_cost_multipliers: Option<&'a [f32]>, // TODO: A slice might not be the best choice when there are many area types.
+    /// Maximum vertical step the agent can take between polygons in world units. Links with a larger [Link::vertical_delta](crate::tiles::Link::vertical_delta) are skipped.
+    ///
+    /// This only constrains connections already in the nav-mesh, it can't add connections excluded by [NavMeshSettings::step_height].
+    pub max_step: Option<f32>,
+    /// Polygon the path starts on if already known, for example from the previous path. Skips searching for the start polygon.
+    ///
+    /// Falls back to searching if the polygon no longer exists or ``start_pos`` isn't on it.
+    pub start_polygon: Option<PolygonRef>,
+    /// Polygon the path ends on if already known. Skips searching for the end polygon.
+    ///
+    /// Falls back to searching if the polygon no longer exists or ``end_pos`` isn't on it.
+    pub end_polygon: Option<PolygonRef>,
+    /// Penalizes polygons close to walls, making the search prefer wider & more central routes even if they are slightly longer. ``0.0`` disables it.
+    ///
+    /// The cost of crossing a polygon is multiplied by ``1.0 + center_bias / clearance`` where clearance is [Polygon::clearance](crate::tiles::Polygon::clearance).
+    pub center_bias: f32,
+    /// Multiplier for the vertical distance when searching for the start & end polygon. If **``None``** is supplied a default value of ``1.0`` is used.
+    ///
+    /// Candidate polygons are ranked by the 3D distance to the closest point on them, values above ``1.0`` penalize vertical mismatch more than horizontal. Use this to keep positions under a bridge on the floor below it.
+    pub vertical_snap_weight: Option<f32>,
+}
+
 /// Performs A* pathfinding on the supplied nav-mesh.
 /// Returning the polygons crossed as a [Vec] containing the tile coordinate ([UVec2]) & polygon index ([u16]) or [FindPathError]
 ///
@@ -48,26 +82,170 @@ pub enum FindPolygonPathError {
 /// * ``end_pos`` - Destination position for the path, i.e where you want to go.
 /// * ``position_search_radius`` - Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
 /// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+///
+/// See [find_polygon_path_with_options] for more options.
 pub fn find_polygon_path(
     nav_mesh: &NavMeshTiles,
     nav_mesh_settings: &NavMeshSettings,
     start_pos: Vec3,
     end_pos: Vec3,
     position_search_radius: Option<f32>,
-    area_cost_multipliers: Option<&[f32]>, // TODO: A slice might not be the best choice when there are many area types.
-) -> Result<Vec<(UVec2, u16)>, FindPolygonPathError> {
-    let search_radius = position_search_radius.unwrap_or(5.0);
+    area_cost_multipliers: Option<&[f32]>,
+) -> Result<Vec<PolygonRef>, FindPolygonPathError> {
+    find_polygon_path_with_options(
+        nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        &FindPathOptions {
+            position_search_radius,
+            area_cost_multipliers,
+            ..Default::default()
+        },
+    )
+}
+
+/// Performs A* pathfinding on the supplied nav-mesh using [FindPathOptions].
+/// Returning the polygons crossed as a [Vec] containing the tile coordinate ([UVec2]) & polygon index ([u16]) or [FindPathError]
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``options`` - Optional parameters for the search.
+pub fn find_polygon_path_with_options(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<Vec<PolygonRef>, FindPolygonPathError> {
+    find_polygon_path_with_cost(nav_mesh, nav_mesh_settings, start_pos, end_pos, options, None).map(|(path, _)| path)
+}
+
+/// Performs A* pathfinding on the supplied nav-mesh using [FindPathOptions], loading tiles missing from ``nav_mesh`` from ``tile_provider``.
+/// Returning the polygons crossed as a [Vec] containing the tile coordinate ([UVec2]) & polygon index ([u16]) or [FindPathError]
+///
+/// When the search reaches a tile whose neighbour isn't in ``nav_mesh``, ``tile_provider`` is called with the neighbour's coordinate. Returned tiles are linked to their neighbours & cached for the rest of the search, then dropped.
+/// ``tile_provider`` is called at most once per coordinate, return ``None`` if the tile doesn't exist.
+///
+/// Use this for streaming worlds where only some tiles are resident, for example by paging in tiles previously baked with [generate_navmesh](crate::generate_navmesh).
+/// Polygons in the returned path may be in tiles that aren't in ``nav_mesh``, so it can't be string pulled with [perform_string_pulling_on_path_in_world_space]. Use [find_path_with_provider] to get a string pulled path.
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh`` & the provided tiles.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``options`` - Optional parameters for the search.
+/// * ``tile_provider`` - Called with the coordinate of a tile missing from ``nav_mesh``.
+pub fn find_polygon_path_with_provider(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+    tile_provider: &dyn Fn(UVec2) -> Option<NavMeshTile>,
+) -> Result<Vec<PolygonRef>, FindPolygonPathError> {
+    find_polygon_path_with_cost(nav_mesh, nav_mesh_settings, start_pos, end_pos, options, Some(tile_provider)).map(|(path, _)| path)
+}
+
+/// Same as [find_polygon_path_with_options] but also returns the cost of the path.
+fn find_polygon_path_with_cost(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+    tile_provider: Option<&dyn Fn(UVec2) -> Option<NavMeshTile>>,
+) -> Result<(Vec<PolygonRef>, f32), FindPolygonPathError> {
+    find_polygon_path_in_tiles(&mut SearchTiles::new(nav_mesh, tile_provider), nav_mesh_settings, start_pos, end_pos, options)
+}
+
+/// Same as [find_polygon_path_with_cost] but searches ``tiles``, which keeps any tiles loaded from the tile provider afterwards.
+fn find_polygon_path_in_tiles(
+    tiles: &mut SearchTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<(Vec<PolygonRef>, f32), FindPolygonPathError> {
+    let PolygonSearch { nodes, last_best_node, .. } = search_polygons(tiles, nav_mesh_settings, start_pos, end_pos, options)?;
+
+    // Is this worth it? :shrug: It saves some memory allocations which I consider important. All locations should also be pretty hot in cache in the next loop.
+    let path_count = {
+        let mut count = 0;
+        let mut parent = Some(last_best_node);
+        while let Some(parent_index) = parent {
+            count += 1;
+            parent = nodes[parent_index].parent;
+        }
+
+        count
+    };
+
+    let mut path = Vec::with_capacity(path_count);
+
+    let mut parent = Some(last_best_node);
+    while let Some(parent_index) = parent {
+        let node = &nodes[parent_index];
+
+        path.push((node.tile, node.polygon));
+
+        parent = node.parent;
+    }
+
+    path.reverse();
+    
+    Ok((path, nodes[last_best_node].cost))
+}
+
+/// Nodes visited by [search_polygons].
+struct PolygonSearch {
+    nodes: Vec<NavMeshNode>,
+    /// Node reaching the end polygon, or the node closest to it if it couldn't be reached.
+    last_best_node: usize,
+    reached_end: bool,
+}
+
+/// Runs the A* search from the polygon at ``start_pos`` to the polygon at ``end_pos``, stopping once the end polygon is reached.
+fn search_polygons(
+    tiles: &mut SearchTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<PolygonSearch, FindPolygonPathError> {
+    let search_radius = options.position_search_radius.unwrap_or(5.0);
+    let vertical_snap_weight = options.vertical_snap_weight.unwrap_or(1.0);
+
+    let start_pos = nav_mesh_settings.to_nav_mesh_space(start_pos);
+    let end_pos = nav_mesh_settings.to_nav_mesh_space(end_pos);
+
+    tiles.load_tile(nav_mesh_settings.get_tile_containing_position(Vec2::new(start_pos.x, start_pos.z)));
+    tiles.load_tile(nav_mesh_settings.get_tile_containing_position(Vec2::new(end_pos.x, end_pos.z)));
 
-    let Some((start_tile, start_poly, start_pos)) = nav_mesh.find_closest_polygon_in_box(nav_mesh_settings, start_pos, search_radius) else {
+    let Some((start_tile, start_poly, start_pos)) = tiles.find_polygon(nav_mesh_settings, start_pos, search_radius, vertical_snap_weight, options.start_polygon) else {
         return Err(FindPolygonPathError::NoValidStartPolygon);
     };
 
-    let Some((end_tile, end_poly, end_pos)) = nav_mesh.find_closest_polygon_in_box(nav_mesh_settings, end_pos, search_radius) else {
+    let Some((end_tile, end_poly, end_pos)) = tiles.find_polygon(nav_mesh_settings, end_pos, search_radius, vertical_snap_weight, options.end_polygon) else {
         return Err(FindPolygonPathError::NoValidEndPolygon);
     };
 
     if start_tile == end_tile && start_poly == end_poly {
-        return Ok(vec![(start_tile, start_poly)]);
+        return Ok(PolygonSearch {
+            nodes: vec![NavMeshNode {
+                position: start_pos,
+                cost: start_pos.distance(end_pos),
+                total_cost: start_pos.distance(end_pos),
+                tile: start_tile,
+                polygon: start_poly,
+                state: NodeState::Closed,
+                parent: None,
+            }],
+            last_best_node: 0,
+            reached_end: true,
+        });
     }
 
     let mut nodes = Vec::with_capacity(10);
@@ -90,6 +268,7 @@ pub fn find_polygon_path(
 
     let mut last_best_node = 0;
     let mut last_best_node_cost = nodes[0].total_cost;
+    let mut reached_end = false;
 
     while let Some(best_node_index) = open_list.pop() {
         let (best_tile, best_polygon, best_position, best_cost, best_parent) = {
@@ -98,6 +277,7 @@ pub fn find_polygon_path(
 
             if node.tile == end_tile && node.polygon == end_poly {
                 last_best_node = best_node_index;
+                reached_end = true;
                 break;
             }
 
@@ -110,19 +290,23 @@ pub fn find_polygon_path(
             )
         };
 
-        let node_tile = nav_mesh.tiles.get(&best_tile).unwrap();
-
-        for link in node_tile.polygons[best_polygon as usize].links.iter() {
-            let (link_tile, link_polygon) = match link {
-                Link::Internal {
-                    neighbour_polygon, ..
-                } => (best_tile, *neighbour_polygon),
-                Link::External {
-                    neighbour_polygon,
-                    direction,
-                    ..
-                } => (direction.offset(best_tile), *neighbour_polygon),
-            };
+        tiles.load_neighbours(best_tile);
+        let node_tile = tiles.get(best_tile).unwrap();
+
+        let center_bias_multiplier = if options.center_bias > 0.0 {
+            let clearance = node_tile.polygons[best_polygon as usize].clearance();
+
+            1.0 + options.center_bias / clearance.max(f32::EPSILON)
+        } else {
+            1.0
+        };
+
+        for link in tiles.links((best_tile, best_polygon)) {
+            if options.max_step.is_some_and(|max_step| link.vertical_delta() > max_step) {
+                continue;
+            }
+
+            let (link_tile, link_polygon) = link.get_neighbour(best_tile);
 
             // Don't go back to our parent.
             if let Some(parent) = best_parent {
@@ -138,37 +322,9 @@ pub fn find_polygon_path(
                 index
             } else {
                 // Node hasn't been visited already, let's create it.
-                let position = match link {
-                    Link::Internal { edge, .. } => {
-                        // Just the midpoint of the current edge.
-                        let indices = &node_tile.polygons[best_polygon as usize].indices;
-                        let a = node_tile.vertices[indices[*edge as usize] as usize];
-                        let b = node_tile.vertices
-                            [indices[(*edge + 1) as usize % indices.len()] as usize];
-
-                        a.lerp(b, 0.5)
-                    }
-                    Link::External {
-                        edge,
-                        bound_min,
-                        bound_max,
-                        ..
-                    } => {
-                        // The mid point of the current-edge sliced by bound_min & bound_max.
-                        let indices = &node_tile.polygons[best_polygon as usize].indices;
-                        let a = node_tile.vertices[indices[*edge as usize] as usize];
-                        let b = node_tile.vertices
-                            [indices[(*edge + 1) as usize % indices.len()] as usize];
-
-                        const S: f32 = 1.0 / 255.0;
-                        let bound_min = *bound_min as f32 * S;
-                        let bound_max = *bound_max as f32 * S;
-                        let clamped_a = a.lerp(b, bound_min);
-                        let clamped_b = a.lerp(b, bound_max);
-
-                        clamped_a.lerp(clamped_b, 0.5)
-                    }
-                };
+                // The midpoint of the part of the edge shared with the neighbour.
+                let (a, b) = node_tile.get_link_portal(&node_tile.polygons[best_polygon as usize], link);
+                let position = a.lerp(b, 0.5);
 
                 nodes.push(NavMeshNode {
                     position,
@@ -186,14 +342,12 @@ pub fn find_polygon_path(
             let (old_state, total_cost) = {
                 let neighbour_node = &mut nodes[neighbour_node_index];
 
-                // TODO: Ideally you want to be able to override this but for now we just go with the distance.
-                let node_cost_multiplier = area_cost_multipliers.map_or(1.0, |multipliers| {
-                    let area = nav_mesh.tiles.get(&best_tile).unwrap().polygons
-                        [best_polygon as usize]
-                        .area;
+                let polygon = &node_tile.polygons[best_polygon as usize];
 
-                    *multipliers.get(area as usize).unwrap_or(&1.0)
-                });
+                // TODO: Ideally you want to be able to override this but for now we just go with the distance.
+                let node_cost_multiplier = options.area_cost_multipliers.map_or(1.0, |multipliers| {
+                    *multipliers.get(polygon.area as usize).unwrap_or(&1.0)
+                }) * center_bias_multiplier;
 
                 let (cost, heuristic) = if end_tile == link_tile && end_poly == link_polygon {
                     // Special case for the final node.
@@ -258,32 +412,140 @@ pub fn find_polygon_path(
         }
     }
 
-    // Is this worth it? :shrug: It saves some memory allocations which I consider important. All locations should also be pretty hot in cache in the next loop.
-    let path_count = {
-        let mut count = 0;
-        let mut parent = Some(last_best_node);
-        while let Some(parent_index) = parent {
-            count += 1;
-            parent = nodes[parent_index].parent;
+    Ok(PolygonSearch {
+        nodes,
+        last_best_node,
+        reached_end,
+    })
+}
+
+/// Tiles accessible to [search_polygons] & [string_pull]. Tiles loaded from the tile provider are kept in ``loaded`` for the duration of the search.
+struct SearchTiles<'a> {
+    nav_mesh: &'a NavMeshTiles,
+    tile_provider: Option<&'a dyn Fn(UVec2) -> Option<NavMeshTile>>,
+    /// Tiles from the tile provider, linked to their neighbours.
+    loaded: NavMeshTiles,
+    /// Links from polygons of ``nav_mesh`` or ``loaded`` to tiles loaded after them, kept here so neither has to be modified.
+    neighbour_links: HashMap<PolygonRef, SmallVec<[Link; VERTICES_IN_TRIANGLE]>>,
+    /// Coordinates already checked for a tile, each is looked up in ``nav_mesh`` or requested from the tile provider only once.
+    requested: HashSet<UVec2>,
+}
+
+impl<'a> SearchTiles<'a> {
+    fn new(nav_mesh: &'a NavMeshTiles, tile_provider: Option<&'a dyn Fn(UVec2) -> Option<NavMeshTile>>) -> Self {
+        Self {
+            nav_mesh,
+            tile_provider,
+            loaded: NavMeshTiles::default(),
+            neighbour_links: HashMap::default(),
+            requested: HashSet::default(),
         }
+    }
 
-        count
-    };
+    fn get(&self, tile_coord: UVec2) -> Option<&NavMeshTile> {
+        self.loaded
+            .tiles
+            .get(&tile_coord)
+            .or_else(|| self.nav_mesh.tiles.get(&tile_coord))
+    }
 
-    let mut path = Vec::with_capacity(path_count);
+    /// Returns the links of ``polygon_ref``, including links to tiles loaded during the search.
+    fn links(&self, polygon_ref: PolygonRef) -> impl Iterator<Item = &Link> {
+        let (tile_coord, polygon) = polygon_ref;
 
-    let mut parent = Some(last_best_node);
-    while let Some(parent_index) = parent {
-        let node = &nodes[parent_index];
+        self.get(tile_coord)
+            .and_then(|tile| tile.polygons.get(polygon as usize))
+            .into_iter()
+            .flat_map(|polygon| polygon.links.iter())
+            .chain(self.neighbour_links.get(&polygon_ref).into_iter().flatten())
+    }
 
-        path.push((node.tile, node.polygon));
+    /// Loads the neighbours of ``tile_coord`` missing from the nav-mesh.
+    fn load_neighbours(&mut self, tile_coord: UVec2) {
+        if self.tile_provider.is_none() {
+            return;
+        }
 
-        parent = node.parent;
+        for neighbour_coord in get_neighbour_coords(tile_coord) {
+            self.load_tile(neighbour_coord);
+        }
     }
 
-    path.reverse();
-    
-    Ok(path)
+    /// Loads ``tile_coord`` from the tile provider if it's missing from the nav-mesh & hasn't been requested already.
+    fn load_tile(&mut self, tile_coord: UVec2) {
+        let Some(tile_provider) = self.tile_provider else {
+            return;
+        };
+        if !self.requested.insert(tile_coord) || self.nav_mesh.tiles.contains_key(&tile_coord) {
+            return;
+        }
+
+        let Some(mut tile) = tile_provider(tile_coord) else {
+            return;
+        };
+
+        // Links stored with the tile may refer to polygons that no longer exist, relink it to the tiles available to this search.
+        for polygon in tile.polygons.iter_mut() {
+            polygon.links.retain(|link| matches!(link, Link::Internal { .. }));
+        }
+
+        let neighbour_links = connect_to_neighbours(&mut tile, tile_coord, |neighbour_coord| self.get(neighbour_coord));
+        for (polygon_ref, link) in neighbour_links {
+            self.neighbour_links.entry(polygon_ref).or_default().push(link);
+        }
+
+        self.loaded.tiles.insert(tile_coord, tile);
+    }
+
+    /// Returns ``known_polygon`` if ``position`` is on it, otherwise searches for the closest polygon.
+    fn find_polygon(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        position: Vec3,
+        search_radius: f32,
+        vertical_snap_weight: f32,
+        known_polygon: Option<PolygonRef>,
+    ) -> Option<(UVec2, u16, Vec3)> {
+        if let Some((tile, polygon)) = known_polygon {
+            let tiles = if self.loaded.tiles.contains_key(&tile) {
+                &self.loaded
+            } else {
+                self.nav_mesh
+            };
+
+            if let Some(point) = tiles.get_point_on_polygon((tile, polygon), position, search_radius) {
+                return Some((tile, polygon, point));
+            }
+        }
+
+        let closest = self.nav_mesh.find_closest_polygon_in_box_weighted(nav_mesh_settings, position, search_radius, vertical_snap_weight);
+        let closest_loaded = self.loaded.find_closest_polygon_in_box_weighted(nav_mesh_settings, position, search_radius, vertical_snap_weight);
+
+        match (closest, closest_loaded) {
+            (Some(closest), Some(closest_loaded)) => {
+                if weighted_distance_squared(closest_loaded.2, position, vertical_snap_weight)
+                    < weighted_distance_squared(closest.2, position, vertical_snap_weight)
+                {
+                    Some(closest_loaded)
+                } else {
+                    Some(closest)
+                }
+            }
+            (closest, closest_loaded) => closest.or(closest_loaded),
+        }
+    }
+}
+
+/// Returns the coordinates of the tiles sharing an edge with ``tile_coord``.
+fn get_neighbour_coords(tile_coord: UVec2) -> impl Iterator<Item = UVec2> {
+    [
+        tile_coord.x.checked_sub(1).map(|x| UVec2::new(x, tile_coord.y)),
+        tile_coord.x.checked_add(1).map(|x| UVec2::new(x, tile_coord.y)),
+        tile_coord.y.checked_sub(1).map(|y| UVec2::new(tile_coord.x, y)),
+        tile_coord.y.checked_add(1).map(|y| UVec2::new(tile_coord.x, y)),
+    ]
+    .into_iter()
+    .flatten()
 }
 
 #[derive(Debug)]
@@ -295,23 +557,52 @@ pub enum StringPullingError {
     NoLinkBetweenPathPoints,
 }
 
-/// Performs "string pulling" on a path of polygons. Used to convert [find_path]'s result to a world space path.
+/// Performs "string pulling" on a path of polygons. Used to convert [find_polygon_path]'s result to a world space path.
 ///
 /// Returns the path as `Vec<Vec3>` or [StringPullingError]
+///
+/// ``start_pos``, ``end_pos`` & the returned path are in nav-mesh space, which is only the same as world space if [NavMeshSettings::up] is [Vec3::Y]. Use [perform_string_pulling_on_path_in_world_space] otherwise.
 pub fn perform_string_pulling_on_path(
     nav_mesh: &NavMeshTiles,
     start_pos: Vec3,
     end_pos: Vec3,
-    path: &[(UVec2, u16)],
+    path: &[PolygonRef],
+) -> Result<Vec<Vec3>, StringPullingError> {
+    string_pull(&SearchTiles::new(nav_mesh, None), start_pos, end_pos, path)
+}
+
+/// Same as [perform_string_pulling_on_path] but ``start_pos``, ``end_pos`` & the returned path are in world space, see [NavMeshSettings::up].
+pub fn perform_string_pulling_on_path_in_world_space(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    path: &[PolygonRef],
+) -> Result<Vec<Vec3>, StringPullingError> {
+    string_pull_in_world_space(
+        &SearchTiles::new(nav_mesh, None),
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        path,
+    )
+}
+
+/// See [perform_string_pulling_on_path], but with positions in nav-mesh space. ``tiles`` holds the tiles of the polygons in ``path``.
+fn string_pull(
+    tiles: &SearchTiles,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    path: &[PolygonRef],
 ) -> Result<Vec<Vec3>, StringPullingError> {
     if path.is_empty() {
         return Err(StringPullingError::PathEmpty);
     }
 
-    let Some(start_tile) = nav_mesh.tiles.get(&path[0].0) else {
+    let Some(start_tile) = tiles.get(path[0].0) else {
         return Err(StringPullingError::MissingStartTile);
     };
-    let Some(end_tile) = nav_mesh.tiles.get(&path.last().unwrap().0) else {
+    let Some(end_tile) = tiles.get(path.last().unwrap().0) else {
         return Err(StringPullingError::MissingEndTile);
     };
 
@@ -336,45 +627,15 @@ pub fn perform_string_pulling_on_path(
             let (left, right) = if let Some(next) = path.get(i + 1) {
                 let current = &path[i];
                 // Find link between this and next in path.
-                let Some(node_tile) = nav_mesh.tiles.get(&current.0) else {
+                let Some(node_tile) = tiles.get(current.0) else {
                     return Err(StringPullingError::MissingNodeTile);
                 };
-                let is_internal = current.0 == next.0;
-                let Some(link) = node_tile.polygons[current.1 as usize].links.iter().find(|link| { // This is a mess :)))
-                    match link {
-                        Link::Internal { neighbour_polygon, .. } => is_internal && next.1 == *neighbour_polygon,
-                        Link::External { neighbour_polygon, direction, .. } => direction.offset(current.0) == next.0 && next.1 == *neighbour_polygon,
-                    }
-                }) else {
+                let polygon = &node_tile.polygons[current.1 as usize];
+                let Some(link) = tiles.links(*current).find(|link| link.get_neighbour(current.0) == *next) else {
                     return Err(StringPullingError::NoLinkBetweenPathPoints);
                 };
 
-                let indices = &node_tile.polygons[current.1 as usize].indices;
-                match link {
-                    Link::Internal { edge, .. } => {
-                        let a = node_tile.vertices[indices[*edge as usize] as usize];
-                        let b = node_tile.vertices
-                            [indices[(*edge + 1) as usize % indices.len()] as usize];
-
-                        (a, b)
-                    }
-                    Link::External {
-                        edge,
-                        bound_min,
-                        bound_max,
-                        ..
-                    } => {
-                        let a = node_tile.vertices[indices[*edge as usize] as usize];
-                        let b = node_tile.vertices
-                            [indices[(*edge + 1) as usize % indices.len()] as usize];
-
-                        const S: f32 = 1.0 / 255.0;
-                        let clamped_a = a.lerp(b, *bound_min as f32 * S);
-                        let clamped_b = a.lerp(b, *bound_max as f32 * S);
-
-                        (clamped_a, clamped_b)
-                    }
-                }
+                node_tile.get_link_portal(polygon, link)
             } else {
                 (end_pos, end_pos)
             };
@@ -436,6 +697,25 @@ pub fn perform_string_pulling_on_path(
     Ok(string_path)
 }
 
+/// Converts ``start_pos`` & ``end_pos`` into nav-mesh space for [string_pull] & the returned path back into world space.
+fn string_pull_in_world_space(
+    tiles: &SearchTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    path: &[PolygonRef],
+) -> Result<Vec<Vec3>, StringPullingError> {
+    let start_pos = nav_mesh_settings.to_nav_mesh_space(start_pos);
+    let end_pos = nav_mesh_settings.to_nav_mesh_space(end_pos);
+
+    string_pull(tiles, start_pos, end_pos, path).map(|string_path| {
+        string_path
+            .into_iter()
+            .map(|point| nav_mesh_settings.to_world_space(point))
+            .collect()
+    })
+}
+
 #[derive(Debug)]
 pub enum FindPathError {
     PolygonPath(FindPolygonPathError),
@@ -451,6 +731,8 @@ pub enum FindPathError {
 /// * ``end_pos`` - Destination position for the path, i.e where you want to go.
 /// * ``position_search_radius`` - Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
 /// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+///
+/// See [find_path_with_options] for more options.
 pub fn find_path(
     nav_mesh: &NavMeshTiles,
     nav_mesh_settings: &NavMeshSettings,
@@ -459,21 +741,306 @@ pub fn find_path(
     position_search_radius: Option<f32>,
     area_cost_multipliers: Option<&[f32]>,
 ) -> Result<Vec<Vec3>, FindPathError>{
-    match find_polygon_path(
+    find_path_with_options(
         nav_mesh,
         nav_mesh_settings,
         start_pos,
         end_pos,
-        position_search_radius,
-        area_cost_multipliers,
+        &FindPathOptions {
+            position_search_radius,
+            area_cost_multipliers,
+            ..Default::default()
+        },
+    )
+}
+
+/// Performs A* pathfinding and string pulling on the supplied nav-mesh using [FindPathOptions].
+/// Returns the path as `Vec<Vec3>` or [FindPathError]
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``options`` - Optional parameters for the search.
+pub fn find_path_with_options(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<Vec<Vec3>, FindPathError>{
+    match find_polygon_path_with_options(
+        nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        options,
     ) {
-        Ok(path) => {
-            perform_string_pulling_on_path(nav_mesh, start_pos, end_pos, &path).map_err(FindPathError::StringPulling)
-        }
+        Ok(path) => string_pull_in_world_space(
+            &SearchTiles::new(nav_mesh, None),
+            nav_mesh_settings,
+            start_pos,
+            end_pos,
+            &path,
+        )
+        .map_err(FindPathError::StringPulling),
         Err(error) => Err(FindPathError::PolygonPath(error)),
     }
 }
 
+/// Performs A* pathfinding and string pulling on the supplied nav-mesh using [FindPathOptions], loading tiles missing from ``nav_mesh`` from ``tile_provider``.
+/// Returns the path as `Vec<Vec3>` or [FindPathError]
+///
+/// Same as [find_polygon_path_with_provider] followed by string pulling, the path is string pulled before the provided tiles are dropped.
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh`` & the provided tiles.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``options`` - Optional parameters for the search.
+/// * ``tile_provider`` - Called with the coordinate of a tile missing from ``nav_mesh``.
+pub fn find_path_with_provider(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+    tile_provider: &dyn Fn(UVec2) -> Option<NavMeshTile>,
+) -> Result<Vec<Vec3>, FindPathError> {
+    let mut tiles = SearchTiles::new(nav_mesh, Some(tile_provider));
+    let (path, _) = find_polygon_path_in_tiles(&mut tiles, nav_mesh_settings, start_pos, end_pos, options)
+        .map_err(FindPathError::PolygonPath)?;
+
+    string_pull_in_world_space(&tiles, nav_mesh_settings, start_pos, end_pos, &path)
+        .map_err(FindPathError::StringPulling)
+}
+
+/// Performs A* pathfinding on the supplied nav-mesh, returning whether ``end_pos`` can be reached from ``start_pos`` or [FindPathError].
+///
+/// Cheaper than [find_path] as the search stops once the end polygon is reached & no path is built or string pulled.
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``start_pos`` - Starting position.
+/// * ``end_pos`` - Destination position.
+/// * ``position_search_radius`` - Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
+/// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+///
+/// See [is_reachable_with_options] for more options.
+pub fn is_reachable(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    position_search_radius: Option<f32>,
+    area_cost_multipliers: Option<&[f32]>,
+) -> Result<bool, FindPathError> {
+    is_reachable_with_options(
+        nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        &FindPathOptions {
+            position_search_radius,
+            area_cost_multipliers,
+            ..Default::default()
+        },
+    )
+}
+
+/// Performs A* pathfinding on the supplied nav-mesh using [FindPathOptions], returning whether ``end_pos`` can be reached from ``start_pos`` or [FindPathError].
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``start_pos`` - Starting position.
+/// * ``end_pos`` - Destination position.
+/// * ``options`` - Optional parameters for the search.
+pub fn is_reachable_with_options(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<bool, FindPathError> {
+    search_polygons(&mut SearchTiles::new(nav_mesh, None), nav_mesh_settings, start_pos, end_pos, options)
+        .map(|search| search.reached_end)
+        .map_err(FindPathError::PolygonPath)
+}
+
+/// Path returned by [find_path_detailed].
+#[derive(Debug, Clone)]
+pub struct DetailedPath {
+    /// Polygons crossed by the path, same as [find_polygon_path_with_options].
+    pub polygons: Vec<PolygonRef>,
+    /// Points of the string pulled path, same as [find_path_with_options].
+    pub points: Vec<Vec3>,
+    /// Cost of the path as calculated by the A* search, including [FindPathOptions::area_cost_multipliers].
+    pub cost: f32,
+}
+
+/// Performs A* pathfinding and string pulling on the supplied nav-mesh using [FindPathOptions].
+/// Returns both the polygons crossed & the string pulled path as a [DetailedPath] or [FindPathError]
+///
+/// Use this instead of calling [find_polygon_path_with_options] & [perform_string_pulling_on_path_in_world_space] when both are needed, for example for debug overlays.
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``options`` - Optional parameters for the search.
+pub fn find_path_detailed(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    options: &FindPathOptions,
+) -> Result<DetailedPath, FindPathError> {
+    let (polygons, cost) = find_polygon_path_with_cost(
+        nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        options,
+        None,
+    )
+    .map_err(FindPathError::PolygonPath)?;
+
+    let points = string_pull_in_world_space(
+        &SearchTiles::new(nav_mesh, None),
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        &polygons,
+    )
+    .map_err(FindPathError::StringPulling)?;
+
+    Ok(DetailedPath {
+        polygons,
+        points,
+        cost,
+    })
+}
+
+/// Returns every polygon whose bounds overlap the axis-aligned box between ``min`` & ``max``.
+///
+/// Only the polygon's bounds are tested, so polygons near the box's corners may be included without overlapping it. Use [NavMeshTiles::get_polygon] to fetch the vertices & area of the returned polygons.
+///
+/// If [NavMeshSettings::up] isn't along an axis the bounds of the box in nav-mesh space are tested instead, which covers more than the box itself.
+///
+/// * ``nav_mesh`` - Nav-mesh to search.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``min`` - Minimum corner of the box.
+/// * ``max`` - Maximum corner of the box.
+pub fn polygons_in_aabb(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    min: Vec3,
+    max: Vec3,
+) -> Vec<PolygonRef> {
+    let up_rotation = nav_mesh_settings.get_up_rotation();
+    let (min, max) = (0..8)
+        .map(|corner| {
+            let corner = BVec3::new(corner & 1 != 0, corner & 2 != 0, corner & 4 != 0);
+
+            up_rotation * Vec3::select(corner, max, min)
+        })
+        .fold((Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)), |(min, max), corner| {
+            (min.min(corner), max.max(corner))
+        });
+
+    polygons_with_bounds_matching(nav_mesh, nav_mesh_settings, min, max, |polygon_min, polygon_max| {
+        polygon_min.cmple(max).all() && polygon_max.cmpge(min).all()
+    })
+}
+
+/// Returns every polygon whose bounds overlap the sphere at ``center`` with ``radius``.
+///
+/// Like [polygons_in_aabb], only the polygon's bounds are tested.
+///
+/// * ``nav_mesh`` - Nav-mesh to search.
+/// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
+/// * ``center`` - Center of the sphere.
+/// * ``radius`` - Radius of the sphere.
+pub fn polygons_in_sphere(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    center: Vec3,
+    radius: f32,
+) -> Vec<PolygonRef> {
+    let center = nav_mesh_settings.to_nav_mesh_space(center);
+    let radius_squared = radius * radius;
+
+    polygons_with_bounds_matching(
+        nav_mesh,
+        nav_mesh_settings,
+        center - radius,
+        center + radius,
+        |polygon_min, polygon_max| {
+            center.clamp(polygon_min, polygon_max).distance_squared(center) <= radius_squared
+        },
+    )
+}
+
+/// Iterates the polygons of the tiles spanned by ``min`` & ``max`` on the XZ-plane, returning those where ``overlaps`` returns true for the polygon's bounds.
+///
+/// Tiles are visited in order of X then Z. If the bounds span more tiles than the nav-mesh contains, the nav-mesh's tiles are visited instead of every coordinate in the bounds.
+fn polygons_with_bounds_matching(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    min: Vec3,
+    max: Vec3,
+    overlaps: impl Fn(Vec3, Vec3) -> bool,
+) -> Vec<PolygonRef> {
+    // Polygons reach all the way to their tile's border, look a cell further so polygons of neighbouring tiles touching the bounds aren't missed.
+    let margin = Vec2::splat(nav_mesh_settings.cell_width);
+    let min_tile = nav_mesh_settings.get_tile_containing_position(Vec2::new(min.x, min.z) - margin);
+    let max_tile = nav_mesh_settings.get_tile_containing_position(Vec2::new(max.x, max.z) + margin);
+
+    // Tile coordinates saturate, a huge box spans billions of coordinates almost none of which have a tile.
+    let tiles_in_bounds = (u64::from(max_tile.x.saturating_sub(min_tile.x)) + 1)
+        .saturating_mul(u64::from(max_tile.y.saturating_sub(min_tile.y)) + 1);
+    let tile_coords: Vec<UVec2> = if tiles_in_bounds > nav_mesh.get_tiles().len() as u64 {
+        let mut tile_coords: Vec<UVec2> = nav_mesh
+            .get_tiles()
+            .keys()
+            .filter(|tile_coord| {
+                tile_coord.cmpge(min_tile).all() && tile_coord.cmple(max_tile).all()
+            })
+            .copied()
+            .collect();
+        tile_coords.sort_unstable_by_key(|tile_coord| (tile_coord.x, tile_coord.y));
+
+        tile_coords
+    } else {
+        (min_tile.x..=max_tile.x)
+            .flat_map(|x| (min_tile.y..=max_tile.y).map(move |y| UVec2::new(x, y)))
+            .collect()
+    };
+
+    let mut polygons = Vec::new();
+    for tile_coord in tile_coords {
+        let Some(tile) = nav_mesh.get_tiles().get(&tile_coord) else {
+            continue;
+        };
+
+        for (polygon_index, polygon) in tile.polygons.iter().enumerate() {
+            let (polygon_min, polygon_max) = polygon.indices.iter().fold(
+                (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+                |(polygon_min, polygon_max), index| {
+                    let vertex = tile.vertices[*index as usize];
+                    (polygon_min.min(vertex), polygon_max.max(vertex))
+                },
+            );
+
+            if overlaps(polygon_min, polygon_max) {
+                polygons.push((tile_coord, polygon_index as u16));
+            }
+        }
+    }
+
+    polygons
+}
+
 fn triangle_area_2d(a: Vec3, b: Vec3, c: Vec3) -> f32 {
     let ab_x = b.x - a.x;
     let ab_z = b.z - a.z;
@@ -13,6 +13,7 @@ const EXPAND_ITERS: u16 = 8;
 const LOG_NB_STACKS: i32 = 3;
 const NB_STACKS: i32 = 1 << LOG_NB_STACKS; // 8.
 
+#[allow(clippy::implicit_saturating_sub)]
 pub fn build_regions(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
     let mut regions = vec![0; open_tile.span_count];
     let mut distances = vec![0; open_tile.span_count];
@@ -753,6 +754,7 @@ fn add_unique_floor_region(region: &mut Region, region_id: u16) {
     region.floors.push(region_id);
 }
 
+#[allow(clippy::too_many_arguments, clippy::implicit_saturating_sub)]
 fn flood_region(
     nav_mesh_settings: &NavMeshSettings,
     entry: LevelStackEntry,
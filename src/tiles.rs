@@ -1,3 +1,5 @@
+use std::fmt::Write;
+
 use bevy::{
     math::Vec3Swizzles,
     prelude::{UVec2, Vec2, Vec3},
@@ -6,11 +8,11 @@ use bevy::{
 use smallvec::SmallVec;
 
 use crate::{
-    mesher::{EdgeConnection, EdgeConnectionDirection, VERTICES_IN_TRIANGLE},
+    mesher::{EdgeConnection, EdgeConnectionDirection, MAX_VERTICES_PER_POLYGON, VERTICES_IN_TRIANGLE},
     NavMeshSettings,
 };
 
-use super::mesher::PolyMesh;
+use super::{heightfields::ClearanceField, mesher::PolyMesh};
 
 /// Representation of a link between different polygons either internal to the tile or external (crossing over to another tile).
 #[derive(Clone, Copy, Debug)]
@@ -20,6 +22,10 @@ pub enum Link {
         edge: u8,
         /// Index of polygon this polygon is linked to.
         neighbour_polygon: u16,
+        /// Largest difference in floor height between the spans on either side of the shared edge. In world units.
+        ///
+        /// Polygons within a tile share their edges' vertices so this comes from the voxelized geometry instead.
+        vertical_delta: f32,
     },
     External {
         /// Edge on self polygon.
@@ -32,15 +38,68 @@ pub enum Link {
         bound_min: u8, // % bound of edge that links to this.
         // MAx % of this edge that connects to the linked polygon.
         bound_max: u8, // For example: 10% -> 50% = the connected edge covers 10% from vertex A to B to 50%.
+        /// Largest vertical distance between this polygon's edge & the linked polygon's edge along the connection. In world units.
+        vertical_delta: f32,
     },
 }
 
-/// A polygon within a nav-mesh tile.
-#[derive(Debug)]
+impl Link {
+    /// Returns the vertical step crossing this link requires in world units, see [Link::Internal] & [Link::External].
+    pub fn vertical_delta(&self) -> f32 {
+        match self {
+            Link::Internal { vertical_delta, .. } | Link::External { vertical_delta, .. } => {
+                *vertical_delta
+            }
+        }
+    }
+
+    /// Returns the polygon this link leads to. ``tile`` is the tile of the polygon the link belongs to.
+    pub fn get_neighbour(&self, tile: UVec2) -> PolygonRef {
+        match self {
+            Link::Internal {
+                neighbour_polygon, ..
+            } => (tile, *neighbour_polygon),
+            Link::External {
+                neighbour_polygon,
+                direction,
+                ..
+            } => (direction.offset(tile), *neighbour_polygon),
+        }
+    }
+}
+
+/// A convex polygon within a nav-mesh tile.
+#[derive(Debug, Clone)]
 pub struct Polygon {
-    pub indices: [u32; VERTICES_IN_TRIANGLE],
+    /// Indices of the polygon's vertices in [NavMeshTile::vertices]. At most [NavMeshSettings::max_vertices_per_polygon].
+    pub indices: SmallVec<[u32; MAX_VERTICES_PER_POLYGON]>,
     pub links: SmallVec<[Link; VERTICES_IN_TRIANGLE]>, // This becomes a mess memory wise with a ton of different small objects around.
     pub area: u16,
+    centroid: Vec3,
+    surface_area: f32,
+    clearance: f32,
+    /// Step height in world units of each edge, the largest step height of the spans along it. Used for links to other tiles, which can't be checked against the spans.
+    edge_step_heights: SmallVec<[f32; MAX_VERTICES_PER_POLYGON]>,
+}
+
+impl Polygon {
+    /// Returns the average of the polygon's vertices in nav-mesh space, use [NavMeshSettings::to_world_space] to convert it.
+    pub fn centroid(&self) -> Vec3 {
+        self.centroid
+    }
+
+    /// Returns the surface area of the polygon in world units, the summed area of its triangle fan.
+    pub fn surface_area(&self) -> f32 {
+        self.surface_area
+    }
+
+    /// Returns the smallest distance on the XZ-plane from the midpoint of one of the polygon's connected edges to the closest wall in world units. [f32::INFINITY] if there are no walls nearby.
+    ///
+    /// Paths enter & leave polygons through their connected edges, so this approximates how much room there is when crossing the polygon.
+    /// Measured from the distance field when the tile is built, so walls further than ``walkable_radius`` cells past the tile's edge aren't seen.
+    pub fn clearance(&self) -> f32 {
+        self.clearance
+    }
 }
 
 /*
@@ -48,24 +107,90 @@ pub struct Polygon {
 */
 
 /// A single nav-mesh tile.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NavMeshTile {
-    /// Vertices in world space.
+    /// Vertices in nav-mesh space, use [NavMeshSettings::to_world_space] to convert them.
     pub vertices: Vec<Vec3>,
     pub polygons: Vec<Polygon>,
-    pub edges: Vec<[EdgeConnection; VERTICES_IN_TRIANGLE]>,
+    pub edges: Vec<SmallVec<[EdgeConnection; MAX_VERTICES_PER_POLYGON]>>,
 }
 impl NavMeshTile {
     /// Returns the closest point on ``polygon`` to ``position``.
     pub fn get_closest_point_in_polygon(&self, polygon: &Polygon, position: Vec3) -> Vec3 {
-        let vertices = polygon.indices.map(|index| self.vertices[index as usize]);
+        let vertices: SmallVec<[Vec3; MAX_VERTICES_PER_POLYGON]> = polygon
+            .indices
+            .iter()
+            .map(|index| self.vertices[*index as usize])
+            .collect();
 
-        if let Some(height) = get_height_in_triangle(&vertices, position) {
+        if let Some(height) = get_height_in_polygon(&vertices, position) {
             return Vec3::new(position.x, height, position.z);
         }
 
         closest_point_on_edges(&vertices, position)
     }
+
+    /// Returns the segment of ``polygon``'s edge shared with the polygon ``link`` leads to.
+    ///
+    /// For external links this is the part of the edge overlapping the neighbouring tile's edge.
+    pub fn get_link_portal(&self, polygon: &Polygon, link: &Link) -> (Vec3, Vec3) {
+        let edge = match link {
+            Link::Internal { edge, .. } | Link::External { edge, .. } => *edge as usize,
+        };
+
+        let a = self.vertices[polygon.indices[edge] as usize];
+        let b = self.vertices[polygon.indices[(edge + 1) % polygon.indices.len()] as usize];
+
+        match link {
+            Link::Internal { .. } => (a, b),
+            Link::External {
+                bound_min,
+                bound_max,
+                ..
+            } => {
+                const S: f32 = 1.0 / 255.0;
+                let bound_min = *bound_min as f32 * S;
+                let bound_max = *bound_max as f32 * S;
+
+                (a.lerp(b, bound_min), a.lerp(b, bound_max))
+            }
+        }
+    }
+}
+
+/// Reference to a polygon in [NavMeshTiles] as a tuple of (tile coordinate, polygon index).
+pub type PolygonRef = (UVec2, u16);
+
+/// Read-only snapshot of the polygons in a nav-mesh & the links between them, created by [NavMeshTiles::build_connectivity_graph].
+///
+/// Any change to the nav-mesh invalidates it.
+#[derive(Default, Debug, Clone)]
+pub struct NavGraph {
+    pub nodes: HashMap<PolygonRef, NavGraphNode>,
+}
+
+/// A polygon in a [NavGraph].
+#[derive(Debug, Clone)]
+pub struct NavGraphNode {
+    /// [Polygon::centroid()] converted to world space.
+    pub centroid: Vec3,
+    /// Same as [Polygon::area].
+    pub area: u16,
+    /// One per link of the polygon, including links to other tiles.
+    pub edges: Vec<NavGraphEdge>,
+}
+
+/// A link from one polygon to another in a [NavGraph].
+#[derive(Debug, Clone, Copy)]
+pub struct NavGraphEdge {
+    /// Polygon the edge leads to.
+    pub to: PolygonRef,
+    /// The shared segment between the polygons in world space, see [NavMeshTile::get_link_portal].
+    pub portal: (Vec3, Vec3),
+    /// Distance from the polygon's centroid through the portal's midpoint to the neighbour's centroid. Area cost multipliers aren't applied.
+    pub cost: f32,
+    /// Same as [Link::vertical_delta].
+    pub vertical_delta: f32,
 }
 
 /// Container for all nav-mesh tiles. Used for pathfinding queries.
@@ -83,16 +208,59 @@ impl NavMeshTiles {
         &self.tiles
     }
 
+    /// Returns how many polygons the tile at ``tile_coord`` contains, or [None] if the tile hasn't been generated.
+    ///
+    /// A generated tile can contain zero polygons, usually because of its settings or every walkable area in it being too small.
+    pub fn get_polygon_count(&self, tile_coord: UVec2) -> Option<usize> {
+        self.tiles.get(&tile_coord).map(|tile| tile.polygons.len())
+    }
+
+    /// Returns the generation of the task which last built or removed the tile at ``tile_coord``, see [GenerationTicker](crate::GenerationTicker).
+    pub fn get_tile_generation(&self, tile_coord: UVec2) -> Option<u64> {
+        self.tile_generations.get(&tile_coord).copied()
+    }
+
+    /// Returns the polygon ``polygon_ref`` refers to, if it exists.
+    pub fn get_polygon(&self, polygon_ref: PolygonRef) -> Option<&Polygon> {
+        self.tiles
+            .get(&polygon_ref.0)
+            .and_then(|tile| tile.polygons.get(polygon_ref.1 as usize))
+    }
+
+    /// Returns the [Polygon::clearance] of the polygon ``polygon_ref`` refers to, if it exists.
+    pub fn get_polygon_clearance(&self, polygon_ref: PolygonRef) -> Option<f32> {
+        self.get_polygon(polygon_ref).map(Polygon::clearance)
+    }
+
+    /// Returns the point on ``polygon_ref`` below or above ``position`` if ``position`` is within the polygon on the XZ-plane and at most ``max_vertical_distance`` from it.
+    pub fn get_point_on_polygon(
+        &self,
+        polygon_ref: PolygonRef,
+        position: Vec3,
+        max_vertical_distance: f32,
+    ) -> Option<Vec3> {
+        let tile = self.tiles.get(&polygon_ref.0)?;
+        let polygon = tile.polygons.get(polygon_ref.1 as usize)?;
+
+        let vertices: SmallVec<[Vec3; MAX_VERTICES_PER_POLYGON]> = polygon
+            .indices
+            .iter()
+            .map(|index| tile.vertices[*index as usize])
+            .collect();
+
+        get_height_in_polygon(&vertices, position)
+            .filter(|height| (height - position.y).abs() <= max_vertical_distance)
+            .map(|height| Vec3::new(position.x, height, position.z))
+    }
+
     pub(super) fn add_tile(
         &mut self,
         tile_coord: UVec2,
         mut tile: NavMeshTile,
-        nav_mesh_settings: &NavMeshSettings,
     ) {
         let previous_tile_existed = self.tiles.contains_key(&tile_coord);
 
         // Connect neighbours.
-        let step_height = nav_mesh_settings.step_height as f32 * nav_mesh_settings.cell_height;
         // X-Negative
         if tile_coord.x > 0 {
             let neighbour_coord = UVec2::new(tile_coord.x - 1, tile_coord.y);
@@ -107,7 +275,6 @@ impl NavMeshTiles {
                     direction,
                     opposite_direction,
                     false,
-                    step_height,
                 );
                 connect_external_links(
                     neighbour,
@@ -115,7 +282,6 @@ impl NavMeshTiles {
                     opposite_direction,
                     direction,
                     previous_tile_existed,
-                    step_height,
                 );
             }
         }
@@ -134,7 +300,6 @@ impl NavMeshTiles {
                     direction,
                     opposite_direction,
                     false,
-                    step_height,
                 );
                 connect_external_links(
                     neighbour,
@@ -142,7 +307,6 @@ impl NavMeshTiles {
                     opposite_direction,
                     direction,
                     previous_tile_existed,
-                    step_height,
                 );
             }
         }
@@ -161,7 +325,6 @@ impl NavMeshTiles {
                     direction,
                     opposite_direction,
                     false,
-                    step_height,
                 );
                 connect_external_links(
                     neighbour,
@@ -169,7 +332,6 @@ impl NavMeshTiles {
                     opposite_direction,
                     direction,
                     previous_tile_existed,
-                    step_height,
                 );
             }
         }
@@ -188,7 +350,6 @@ impl NavMeshTiles {
                     direction,
                     opposite_direction,
                     false,
-                    step_height,
                 );
                 connect_external_links(
                     neighbour,
@@ -196,7 +357,6 @@ impl NavMeshTiles {
                     opposite_direction,
                     direction,
                     previous_tile_existed,
-                    step_height,
                 );
             }
         }
@@ -245,12 +405,122 @@ impl NavMeshTiles {
         self.tiles.remove(&tile_coord);
     }
 
+    /// Builds the dual graph of the nav-mesh: polygons as nodes & their links as edges.
+    ///
+    /// Edges are created from the same [Polygon::links] & [NavMeshTile::get_link_portal] used by [find_polygon_path](crate::query::find_polygon_path),
+    /// which searches between the portals' midpoints where this graph measures the cost between polygon centroids.
+    /// Positions are converted from nav-mesh space to world space with [NavMeshSettings::to_world_space].
+    pub fn build_connectivity_graph(&self, nav_mesh_settings: &NavMeshSettings) -> NavGraph {
+        let mut nodes = HashMap::with_capacity(self.tiles.values().map(|tile| tile.polygons.len()).sum());
+
+        for (tile_coord, tile) in self.tiles.iter() {
+            for (polygon_index, polygon) in tile.polygons.iter().enumerate() {
+                let edges = polygon
+                    .links
+                    .iter()
+                    .filter_map(|link| {
+                        let to = link.get_neighbour(*tile_coord);
+                        let neighbour = self.get_polygon(to)?;
+
+                        let (a, b) = tile.get_link_portal(polygon, link);
+                        let portal = (nav_mesh_settings.to_world_space(a), nav_mesh_settings.to_world_space(b));
+                        let midpoint = portal.0.lerp(portal.1, 0.5);
+                        let centroid = nav_mesh_settings.to_world_space(polygon.centroid);
+                        let neighbour_centroid = nav_mesh_settings.to_world_space(neighbour.centroid);
+
+                        Some(NavGraphEdge {
+                            to,
+                            portal,
+                            cost: centroid.distance(midpoint) + midpoint.distance(neighbour_centroid),
+                            vertical_delta: link.vertical_delta(),
+                        })
+                    })
+                    .collect();
+
+                nodes.insert(
+                    (*tile_coord, polygon_index as u16),
+                    NavGraphNode {
+                        centroid: nav_mesh_settings.to_world_space(polygon.centroid),
+                        area: polygon.area,
+                        edges,
+                    },
+                );
+            }
+        }
+
+        NavGraph { nodes }
+    }
+
+    /// Writes every tile to a Wavefront OBJ string. Useful for inspecting the nav-mesh in external tools.
+    ///
+    /// Each tile is a separate object & polygons are fan-triangulated into groups by their area type (``area_<area>``).
+    ///
+    /// Takes [NavMeshSettings] as tiles are stored in nav-mesh space, vertices are converted to world space with [NavMeshSettings::to_world_space].
+    pub fn export_obj(&self, nav_mesh_settings: &NavMeshSettings) -> String {
+        let mut tile_coords: Vec<_> = self.tiles.keys().copied().collect();
+        tile_coords.sort_by_key(|tile_coord| (tile_coord.x, tile_coord.y));
+
+        let mut obj = String::new();
+        let mut vertex_offset = 1; // OBJ indices start at 1.
+        for tile_coord in tile_coords {
+            let tile = &self.tiles[&tile_coord];
+
+            let _ = writeln!(obj, "o tile_{}_{}", tile_coord.x, tile_coord.y);
+            for vertex in tile.vertices.iter() {
+                let vertex = nav_mesh_settings.to_world_space(*vertex);
+                let _ = writeln!(obj, "v {} {} {}", vertex.x, vertex.y, vertex.z);
+            }
+
+            let mut current_area = None;
+            for polygon in tile.polygons.iter() {
+                if current_area != Some(polygon.area) {
+                    current_area = Some(polygon.area);
+                    let _ = writeln!(obj, "g area_{}", polygon.area);
+                }
+
+                let first = polygon.indices[0] as usize + vertex_offset;
+                for i in 1..(polygon.indices.len() - 1) {
+                    let _ = writeln!(
+                        obj,
+                        "f {} {} {}",
+                        first,
+                        polygon.indices[i] as usize + vertex_offset,
+                        polygon.indices[i + 1] as usize + vertex_offset
+                    );
+                }
+            }
+
+            vertex_offset += tile.vertices.len();
+        }
+
+        obj
+    }
+
     /// Returns the closest polygon in a box around ``center`` as a tuple of (tile coordinate, polygon index, position on triangle).
+    ///
+    /// Polygons are ranked by the 3D distance from ``center`` to the closest point on them.
+    ///
+    /// ``center`` & the returned position are in nav-mesh space, use [NavMeshSettings::to_nav_mesh_space] & [NavMeshSettings::to_world_space] to convert them if [NavMeshSettings::up] isn't [Vec3::Y].
     pub fn find_closest_polygon_in_box(
         &self,
         nav_mesh_settings: &NavMeshSettings,
         center: Vec3,
         half_extents: f32,
+    ) -> Option<(UVec2, u16, Vec3)> {
+        self.find_closest_polygon_in_box_weighted(nav_mesh_settings, center, half_extents, 1.0)
+    }
+
+    /// Same as [NavMeshTiles::find_closest_polygon_in_box] but the vertical distance is multiplied by ``vertical_weight`` when ranking polygons.
+    ///
+    /// A ``vertical_weight`` above ``1.0`` penalizes vertical mismatch more than horizontal, preferring the polygon directly below or above ``center`` over one that is horizontally closer on another floor.
+    ///
+    /// Like [NavMeshTiles::find_closest_polygon_in_box], ``center`` & the returned position are in nav-mesh space.
+    pub fn find_closest_polygon_in_box_weighted(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        center: Vec3,
+        half_extents: f32,
+        vertical_weight: f32,
     ) -> Option<(UVec2, u16, Vec3)> {
         let min = center - half_extents;
         let max = center + half_extents;
@@ -266,7 +536,7 @@ impl NavMeshTiles {
                 if let Some(tile) = self.tiles.get(&tile_coords) {
                     for (poly_i, polygon) in tile.polygons.iter().enumerate() {
                         let closest_point = tile.get_closest_point_in_polygon(polygon, center);
-                        let closest_distance = closest_point.distance_squared(center);
+                        let closest_distance = weighted_distance_squared(closest_point, center, vertical_weight);
 
                         if closest_distance < out_distance {
                             out_distance = closest_distance;
@@ -281,15 +551,25 @@ impl NavMeshTiles {
     }
 }
 
-fn get_height_in_triangle(vertices: &[Vec3; VERTICES_IN_TRIANGLE], position: Vec3) -> Option<f32> {
+/// Squared distance between ``a`` & ``b`` with the vertical distance multiplied by ``vertical_weight``.
+pub(crate) fn weighted_distance_squared(a: Vec3, b: Vec3, vertical_weight: f32) -> f32 {
+    let delta = a - b;
+
+    delta.xz().length_squared() + (delta.y * vertical_weight).powi(2)
+}
+
+fn get_height_in_polygon(vertices: &[Vec3], position: Vec3) -> Option<f32> {
     if !in_polygon(vertices, position) {
         return None;
     }
 
-    if let Some(height) =
-        closest_height_in_triangle(vertices[0], vertices[1], vertices[2], position)
-    {
-        return Some(height);
+    // Polygons are convex so we can check each triangle of a fan.
+    for i in 1..(vertices.len() - 1) {
+        if let Some(height) =
+            closest_height_in_triangle(vertices[0], vertices[i], vertices[i + 1], position)
+        {
+            return Some(height);
+        }
     }
 
     // We only hit this if we are ON an edge. Unlikely to happen.
@@ -325,7 +605,7 @@ fn closest_height_in_triangle(a: Vec3, b: Vec3, c: Vec3, position: Vec3) -> Opti
     None
 }
 
-fn closest_point_on_edges(vertices: &[Vec3; VERTICES_IN_TRIANGLE], position: Vec3) -> Vec3 {
+fn closest_point_on_edges(vertices: &[Vec3], position: Vec3) -> Vec3 {
     let mut d_min = f32::INFINITY;
     let mut t_min = 0.0;
 
@@ -367,7 +647,7 @@ fn distance_point_to_segment_2d(point: Vec3, seg_a: Vec3, seg_b: Vec3) -> (f32,
     (dx * dx + dz * dz, t)
 }
 
-fn in_polygon(vertices: &[Vec3; VERTICES_IN_TRIANGLE], position: Vec3) -> bool {
+fn in_polygon(vertices: &[Vec3], position: Vec3) -> bool {
     let mut inside = false;
 
     for i in 0..vertices.len() {
@@ -394,22 +674,87 @@ fn remove_links_to_direction(tile: &mut NavMeshTile, remove_direction: EdgeConne
     }
 }
 
+/// Links ``tile`` to the neighbouring tiles ``get_tile`` returns without modifying them, returning the links from the neighbours' polygons back to ``tile`` instead.
+///
+/// Used where the neighbours can't be borrowed mutably, like tiles loaded during a search that mustn't change the nav-mesh.
+pub(super) fn connect_to_neighbours<'a>(
+    tile: &mut NavMeshTile,
+    tile_coord: UVec2,
+    get_tile: impl Fn(UVec2) -> Option<&'a NavMeshTile>,
+) -> Vec<(PolygonRef, Link)> {
+    let neighbours = [
+        (
+            tile_coord.x.checked_sub(1).map(|x| UVec2::new(x, tile_coord.y)),
+            EdgeConnectionDirection::XNegative,
+            EdgeConnectionDirection::XPositive,
+        ),
+        (
+            tile_coord.x.checked_add(1).map(|x| UVec2::new(x, tile_coord.y)),
+            EdgeConnectionDirection::XPositive,
+            EdgeConnectionDirection::XNegative,
+        ),
+        (
+            tile_coord.y.checked_sub(1).map(|y| UVec2::new(tile_coord.x, y)),
+            EdgeConnectionDirection::ZNegative,
+            EdgeConnectionDirection::ZPositive,
+        ),
+        (
+            tile_coord.y.checked_add(1).map(|y| UVec2::new(tile_coord.x, y)),
+            EdgeConnectionDirection::ZPositive,
+            EdgeConnectionDirection::ZNegative,
+        ),
+    ];
+
+    let mut neighbour_links = Vec::new();
+    for (neighbour_coord, direction, opposite_direction) in neighbours {
+        let Some(neighbour_coord) = neighbour_coord else {
+            continue;
+        };
+        let Some(neighbour) = get_tile(neighbour_coord) else {
+            continue;
+        };
+
+        connect_external_links(tile, neighbour, direction, opposite_direction, false);
+        neighbour_links.extend(
+            find_external_links(neighbour, tile, opposite_direction, direction)
+                .into_iter()
+                .map(|(polygon, link)| ((neighbour_coord, polygon as u16), link)),
+        );
+    }
+
+    neighbour_links
+}
+
 fn connect_external_links(
     tile: &mut NavMeshTile,
     neighbour: &NavMeshTile,
     neighbour_direction: EdgeConnectionDirection,
     neighbour_to_self_direction: EdgeConnectionDirection,
     remove_existing_links: bool,
-    step_height: f32,
 ) {
-    for (poly_index, polygon) in tile.polygons.iter_mut().enumerate() {
-        if remove_existing_links {
-            polygon.links.retain(|link| match link {
-                Link::Internal { .. } => true,
-                Link::External { direction, .. } => *direction != neighbour_direction,
-            });
-        }
+    if remove_existing_links {
+        remove_links_to_direction(tile, neighbour_direction);
+    }
+
+    for (poly_index, link) in find_external_links(
+        tile,
+        neighbour,
+        neighbour_direction,
+        neighbour_to_self_direction,
+    ) {
+        tile.polygons[poly_index].links.push(link);
+    }
+}
 
+/// Returns the links from ``tile``'s polygons to the polygons of ``neighbour`` as (polygon index, link) pairs, without modifying either tile.
+fn find_external_links(
+    tile: &NavMeshTile,
+    neighbour: &NavMeshTile,
+    neighbour_direction: EdgeConnectionDirection,
+    neighbour_to_self_direction: EdgeConnectionDirection,
+) -> Vec<(usize, Link)> {
+    let mut links = Vec::new();
+    for (poly_index, polygon) in tile.polygons.iter().enumerate() {
         for (edge_index, edge) in tile.edges[poly_index].iter().enumerate() {
             let EdgeConnection::External(edge_direction) = edge else {
                 continue;
@@ -422,19 +767,19 @@ fn connect_external_links(
             let vertex_b =
                 tile.vertices[polygon.indices[(edge_index + 1) % polygon.indices.len()] as usize];
 
-            let (connection_count, connected_polys, connection_areas) =
+            let (connection_count, connected_polys, connection_areas, vertical_deltas) =
                 find_connecting_polygons_in_tile(
                     &vertex_a,
                     &vertex_b,
                     neighbour,
                     neighbour_to_self_direction,
-                    step_height,
+                    polygon.edge_step_heights[edge_index],
                 );
 
-            polygon.links.reserve(connection_count);
             for i in 0..connection_count {
                 let neighbour_polygon = connected_polys[i];
                 let area = connection_areas[i];
+                let vertical_delta = vertical_deltas[i];
 
                 let (mut bound_min, mut bound_max) = if neighbour_to_self_direction
                     == EdgeConnectionDirection::XNegative
@@ -458,17 +803,20 @@ fn connect_external_links(
                 let min_byte = (bound_min.clamp(0.0, 1.0) * 255.0).round() as u8;
                 let max_byte = (bound_max.clamp(0.0, 1.0) * 255.0).round() as u8;
 
-                polygon.links.push(Link::External {
+                links.push((poly_index, Link::External {
                     edge: edge_index as u8,
                     neighbour_polygon,
                     direction: neighbour_direction,
                     bound_min: min_byte,
                     bound_max: max_byte,
-                });
+                    vertical_delta,
+                }));
             }
-            break; // We can only have one edge parallel to the direction in a triangle.
+            break; // We can only have one edge parallel to the direction in a convex polygon.
         }
     }
+
+    links
 }
 
 fn calculate_slab_end_points(
@@ -510,6 +858,7 @@ fn get_slab_position(vertex: &Vec3, side: EdgeConnectionDirection) -> f32 {
     }
 }
 
+/// Returns the largest vertical distance between the slabs where they overlap, if they overlap & are within ``allowed_step`` of each other.
 fn check_slabs_overlap(
     a_min: Vec2,
     a_max: Vec2,
@@ -517,18 +866,18 @@ fn check_slabs_overlap(
     b_max: Vec2,
     edge_shrink: f32,
     allowed_step: f32,
-) -> bool {
+) -> Option<f32> {
     let min_edge = (a_min.x + edge_shrink).max(b_min.x + edge_shrink);
     let max_edge = (a_max.x - edge_shrink).min(b_max.x - edge_shrink);
     if min_edge > max_edge {
-        return false;
+        return None;
     }
 
     let a_d = (a_max.y - a_min.y) / (a_max.x - a_min.x);
     let a_k = a_min.y - a_d * a_min.x;
 
     let b_d = (b_max.y - b_min.y) / (b_max.x - b_min.x);
-    let b_k = b_min.y - a_d * b_min.x;
+    let b_k = b_min.y - b_d * b_min.x;
 
     let a_min_y = a_d * min_edge + a_k;
     let a_max_y = a_d * max_edge + a_k;
@@ -539,13 +888,20 @@ fn check_slabs_overlap(
     let delta_min = b_min_y - a_min_y;
     let delta_max = b_max_y - a_max_y;
 
+    let vertical_delta = delta_min.abs().max(delta_max.abs());
+
     if delta_min * delta_max < 0.0 {
-        return true;
+        // The slabs cross each other.
+        return Some(vertical_delta);
     }
 
     let threshold = (allowed_step * 2.0).powi(2);
 
-    delta_min * delta_min <= threshold || delta_max * delta_max <= threshold
+    if delta_min * delta_min <= threshold || delta_max * delta_max <= threshold {
+        Some(vertical_delta)
+    } else {
+        None
+    }
 }
 
 const MAX_CONNECTING_POLYGONS: usize = 8;
@@ -560,9 +916,11 @@ fn find_connecting_polygons_in_tile(
     usize,
     [u16; MAX_CONNECTING_POLYGONS],
     [Vec2; MAX_CONNECTING_POLYGONS],
+    [f32; MAX_CONNECTING_POLYGONS],
 ) {
     let mut connecting_polys = [0; MAX_CONNECTING_POLYGONS];
     let mut connection_area = [Vec2::ZERO; MAX_CONNECTING_POLYGONS];
+    let mut vertical_deltas = [0.0; MAX_CONNECTING_POLYGONS];
     let mut count = 0;
 
     let (in_min, in_max) = calculate_slab_end_points(vertex_a, vertex_b, side);
@@ -588,28 +946,46 @@ fn find_connecting_polygons_in_tile(
             }
             let (edge_min, edge_max) = calculate_slab_end_points(&vertex_c, &vertex_d, side);
 
-            if !check_slabs_overlap(in_min, in_max, edge_min, edge_max, 0.01, step_height) {
+            // The more permissive step height of the two edges, like spans within a tile.
+            let step_height = step_height.max(polygon.edge_step_heights[edge_index]);
+            let Some(vertical_delta) = check_slabs_overlap(in_min, in_max, edge_min, edge_max, 0.01, step_height) else {
                 continue;
-            }
+            };
 
             if count < connecting_polys.len() {
                 connecting_polys[count] = poly_index as u16;
                 connection_area[count] =
                     Vec2::new(in_min.x.max(edge_min.x), in_max.x.min(edge_max.x));
+                vertical_deltas[count] = vertical_delta;
                 count += 1;
             }
             break;
         }
     }
 
-    (count, connecting_polys, connection_area)
+    (count, connecting_polys, connection_area, vertical_deltas)
 }
 
 pub(super) fn create_nav_mesh_tile_from_poly_mesh(
     poly_mesh: PolyMesh,
+    clearance_field: &ClearanceField,
     tile_coord: UVec2,
     nav_mesh_settings: &NavMeshSettings,
 ) -> NavMeshTile {
+    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+    let vertices: Vec<Vec3> = poly_mesh
+        .vertices
+        .iter()
+        .map(|vertex| {
+            Vec3::new(
+                tile_origin.x + vertex.x as f32 * nav_mesh_settings.cell_width,
+                nav_mesh_settings.world_bottom_bound
+                    + vertex.y as f32 * nav_mesh_settings.cell_height,
+                tile_origin.y + vertex.z as f32 * nav_mesh_settings.cell_width,
+            )
+        })
+        .collect();
+
     // Slight worry that the compiler won't optimize this but damn, it's cool.
     let polygons = poly_mesh
         .polygons
@@ -626,35 +1002,75 @@ pub(super) fn create_nav_mesh_tile_from_poly_mesh(
                         return None;
                     };
 
+                    let a = poly_mesh.vertices[indices[i] as usize].as_vec3();
+                    let b = poly_mesh.vertices[indices[(i + 1) % indices.len()] as usize].as_vec3();
+
                     Some(Link::Internal {
                         edge: i as u8,
                         neighbour_polygon: *other_polygon,
+                        vertical_delta: clearance_field.get_vertical_delta(a, b, nav_mesh_settings)
+                            as f32
+                            * nav_mesh_settings.cell_height,
                     })
                 })
                 .collect();
 
+            let first = vertices[indices[0] as usize];
+            let centroid = indices
+                .iter()
+                .map(|index| vertices[*index as usize])
+                .sum::<Vec3>()
+                / indices.len() as f32;
+            let surface_area = indices
+                .windows(2)
+                .skip(1)
+                .map(|edge| {
+                    let a = vertices[edge[0] as usize] - first;
+                    let b = vertices[edge[1] as usize] - first;
+
+                    a.cross(b).length() * 0.5
+                })
+                .sum();
+            let clearance = edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| **edge != EdgeConnection::None)
+                .map(|(i, _)| {
+                    let a = poly_mesh.vertices[indices[i] as usize].as_vec3();
+                    let b = poly_mesh.vertices[indices[(i + 1) % indices.len()] as usize].as_vec3();
+
+                    clearance_field.get_clearance(a.lerp(b, 0.5), nav_mesh_settings)
+                })
+                .fold(f32::INFINITY, f32::min);
+            let edge_step_heights = edges
+                .iter()
+                .enumerate()
+                .map(|(i, edge)| {
+                    let step_height = if matches!(edge, EdgeConnection::External(_)) {
+                        let a = poly_mesh.vertices[indices[i] as usize].as_vec3();
+                        let b = poly_mesh.vertices[indices[(i + 1) % indices.len()] as usize].as_vec3();
+
+                        clearance_field.get_step_height(a, b, nav_mesh_settings)
+                    } else {
+                        nav_mesh_settings.step_height
+                    };
+
+                    step_height as f32 * nav_mesh_settings.cell_height
+                })
+                .collect();
+
             Polygon {
                 links,
-                indices: *indices,
+                indices: indices.clone(),
                 area: *area,
+                centroid,
+                surface_area,
+                clearance,
+                edge_step_heights,
             }
         })
         .collect();
 
-    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
-    let vertices = poly_mesh
-        .vertices
-        .iter()
-        .map(|vertex| {
-            Vec3::new(
-                tile_origin.x + vertex.x as f32 * nav_mesh_settings.cell_width,
-                nav_mesh_settings.world_bottom_bound
-                    + vertex.y as f32 * nav_mesh_settings.cell_height,
-                tile_origin.y + vertex.z as f32 * nav_mesh_settings.cell_width,
-            )
-        })
-        .collect();
-
     NavMeshTile {
         vertices,
         edges: poly_mesh.edges,
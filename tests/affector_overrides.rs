@@ -0,0 +1,100 @@
+mod common;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_path, find_polygon_path},
+    NavMesh, NavMeshAffectorOverrides, NavMeshSettings,
+};
+
+/// Height of the polygon a path from the floor towards the top of the block ends on.
+fn path_end_height(app: &App) -> f32 {
+    let settings = app.world.resource::<NavMeshSettings>();
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+
+    let path = find_polygon_path(
+        &nav_mesh,
+        settings,
+        Vec3::new(-4.0, 0.1, 0.3),
+        Vec3::new(4.0, 0.6, 0.3),
+        None,
+        None,
+    )
+    .unwrap();
+
+    nav_mesh
+        .get_polygon(*path.last().unwrap())
+        .unwrap()
+        .centroid()
+        .y
+}
+
+#[test]
+fn removing_overrides_restores_global_settings() {
+    let mut app = app();
+    spawn_affector(
+        &mut app,
+        Transform::IDENTITY,
+        Collider::cuboid(10.0, 0.1, 10.0),
+    );
+    // A block 0.5 above the floor, too tall for the global step height of 0.3.
+    let block = spawn_affector(
+        &mut app,
+        Transform::from_xyz(4.0, 0.35, 0.0),
+        Collider::cuboid(3.0, 0.25, 3.0),
+    );
+    settle(&mut app);
+    assert!(path_end_height(&app) < 0.2);
+
+    app.world
+        .entity_mut(block)
+        .insert(NavMeshAffectorOverrides {
+            step_height: Some(6),
+            ..Default::default()
+        });
+    settle(&mut app);
+    assert!(path_end_height(&app) > 0.3);
+
+    app.world
+        .entity_mut(block)
+        .remove::<NavMeshAffectorOverrides>();
+    settle(&mut app);
+    assert!(path_end_height(&app) < 0.2);
+}
+
+#[test]
+fn step_height_override_connects_tiles() {
+    let settings = settings();
+    // A floor ending at the tile border at x = 0 & a block 0.5 higher starting there, too tall for the global step height of 0.3.
+    let floor = Collider::cuboid(5.0, 0.05, 5.0);
+    let floor_transform = GlobalTransform::from_xyz(-5.0, 0.05, 0.3);
+    let block = Collider::cuboid(5.0, 0.3, 5.0);
+    let block_transform = GlobalTransform::from_xyz(5.0, 0.3, 0.3);
+
+    let (start, end) = (Vec3::new(-4.0, 0.1, 0.0), Vec3::new(4.0, 0.6, 0.0));
+    assert_ne!(
+        settings.get_tile_containing_position(start.xz()),
+        settings.get_tile_containing_position(end.xz())
+    );
+    let reaches_block = |overrides: Option<&NavMeshAffectorOverrides>| {
+        let tiles = generate_navmesh(
+            &settings,
+            [
+                (&floor, &floor_transform, None, None),
+                (&block, &block_transform, None, overrides),
+            ],
+        );
+
+        find_path(&tiles, &settings, start, end, Some(0.5), None)
+            .is_ok_and(|path| path.last().unwrap().distance(end) < 0.5)
+    };
+
+    assert!(!reaches_block(None));
+    assert!(reaches_block(Some(&NavMeshAffectorOverrides {
+        step_height: Some(6),
+        ..Default::default()
+    })));
+}
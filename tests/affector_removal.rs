@@ -0,0 +1,65 @@
+mod common;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{NavMesh, NavMeshAffectorDisabled};
+
+#[test]
+fn despawning_sole_affector_removes_its_tile() {
+    let mut app = app();
+    let position = Vec3::new(60.0, 0.0, 60.0);
+    let affector = spawn_affector(
+        &mut app,
+        Transform::from_translation(position),
+        Collider::cuboid(2.0, 0.1, 2.0),
+    );
+    settle(&mut app);
+
+    let tile = settings().get_tile_containing_position(position.xz());
+    {
+        let nav_mesh = app.world.resource::<NavMesh>().get();
+        let nav_mesh = nav_mesh.read().unwrap();
+        assert_eq!(nav_mesh.get_tiles().keys().collect::<Vec<_>>(), [&tile]);
+    }
+
+    app.world.despawn(affector);
+    settle(&mut app);
+
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    assert!(nav_mesh.get_tiles().is_empty());
+}
+
+#[test]
+fn disabling_affector_removes_its_polygons_until_enabled() {
+    let mut app = app();
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(5.0, 0.0, 5.0),
+        Collider::cuboid(4.0, 0.1, 4.0),
+    );
+    settle(&mut app);
+    let without_plate = snapshot(&app);
+
+    // A second plate in the same tile, away from the first.
+    let plate = spawn_affector(
+        &mut app,
+        Transform::from_xyz(18.0, 0.0, 18.0),
+        Collider::cuboid(4.0, 0.1, 4.0),
+    );
+    settle(&mut app);
+    let with_plate = snapshot(&app);
+    assert_eq!(with_plate.len(), 1);
+    assert!(with_plate[0].2.len() > without_plate[0].2.len());
+
+    app.world.entity_mut(plate).insert(NavMeshAffectorDisabled);
+    settle(&mut app);
+    assert_eq!(snapshot(&app), without_plate);
+
+    app.world
+        .entity_mut(plate)
+        .remove::<NavMeshAffectorDisabled>();
+    settle(&mut app);
+    assert_eq!(snapshot(&app), with_plate);
+}
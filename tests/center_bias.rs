@@ -0,0 +1,87 @@
+mod common;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_path_detailed, FindPathOptions},
+};
+
+#[test]
+fn center_bias_prefers_wide_gap() {
+    let settings = settings();
+    let floor = (Collider::cuboid(10.0, 0.1, 10.0), GlobalTransform::IDENTITY);
+    // A wall along the tile border at x = 0 with a narrow gap at z in [-0.75, 0.75] & a wide gap at z in [4, 9.5].
+    let walls: Vec<_> = [(-10.0_f32, -0.75_f32), (0.75, 4.0), (9.5, 10.0)]
+        .into_iter()
+        .map(|(min_z, max_z)| {
+            (
+                Collider::cuboid(0.5, 1.0, (max_z - min_z) / 2.0),
+                GlobalTransform::from_translation(Vec3::new(0.0, 1.0, (min_z + max_z) / 2.0)),
+            )
+        })
+        .collect();
+    let tiles = generate_navmesh(
+        &settings,
+        std::iter::once(&floor)
+            .chain(walls.iter())
+            .map(|(collider, transform)| (collider, transform, None, None)),
+    );
+
+    let (start, end) = (Vec3::new(-5.0, 0.2, 0.3), Vec3::new(5.0, 0.2, 0.3));
+    let furthest_z = |center_bias| {
+        let path = find_path_detailed(
+            &tiles,
+            &settings,
+            start,
+            end,
+            &FindPathOptions {
+                center_bias,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        path.points.iter().map(|point| point.z).fold(0.0, f32::max)
+    };
+
+    // Without the bias the path goes straight through the narrow gap.
+    assert!(furthest_z(0.0) < 1.0);
+    assert!(furthest_z(10.0) > 2.5);
+}
+
+#[test]
+fn clearance_includes_walls_in_tile_border() {
+    let settings = settings();
+    let floor = (Collider::cuboid(10.0, 0.1, 10.0), GlobalTransform::IDENTITY);
+    // A pillar in the tile at -X, reaching into the border of the tile at +X which is ``walkable_radius`` cells past x = 0.
+    let pillar = (
+        Collider::cuboid(0.3, 1.0, 0.5),
+        GlobalTransform::from_translation(Vec3::new(-0.4, 1.0, 5.0)),
+    );
+
+    // Clearance of the polygon just across the tile border from the pillar.
+    let position = Vec3::new(0.1, 0.1, 5.0);
+    let clearance_next_to_pillar = |colliders: &[&(Collider, GlobalTransform)]| {
+        let tiles = generate_navmesh(
+            &settings,
+            colliders
+                .iter()
+                .map(|(collider, transform)| (collider, transform, None, None)),
+        );
+        let (tile, polygon, _) = tiles
+            .find_closest_polygon_in_box(&settings, position, 0.5)
+            .unwrap();
+        assert_eq!(tile, settings.get_tile_containing_position(position.xz()));
+
+        tiles.get_polygon_clearance((tile, polygon)).unwrap()
+    };
+
+    let without_pillar = clearance_next_to_pillar(&[&floor]);
+    let with_pillar = clearance_next_to_pillar(&[&floor, &pillar]);
+    assert!(
+        with_pillar < 1.0 && with_pillar < without_pillar,
+        "{with_pillar} {without_pillar}"
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{generate_navmesh, NavMesh};
+
+#[test]
+fn scaled_trimesh_matches_scaled_extents() {
+    // An 8x8 quad, scaled to 16x12 centered on (1, 1, 1) so it crosses tile borders.
+    let collider = Collider::trimesh(
+        vec![
+            Vec3::new(-4.0, 0.0, -4.0),
+            Vec3::new(4.0, 0.0, -4.0),
+            Vec3::new(4.0, 0.0, 4.0),
+            Vec3::new(-4.0, 0.0, 4.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    );
+    let transform = Transform::from_xyz(1.0, 1.0, 1.0).with_scale(Vec3::new(2.0, 1.0, 1.5));
+
+    let tiles = generate_navmesh(
+        &settings(),
+        [(&collider, &GlobalTransform::from(transform), None, None)],
+    );
+    let (min, max) = polygon_extents(&tiles);
+    // Erosion by walkable_radius pulls the edges in by a cell or two.
+    let tolerance = 0.6;
+    assert!(
+        (min.x - -7.0).abs() < tolerance && (max.x - 9.0).abs() < tolerance,
+        "{min} {max}"
+    );
+    assert!(
+        (min.z - -5.0).abs() < tolerance && (max.z - 7.0).abs() < tolerance,
+        "{min} {max}"
+    );
+    assert!(
+        (min.y - 1.0).abs() < 0.2 && (max.y - 1.0).abs() < 0.2,
+        "{min} {max}"
+    );
+
+    // The plugin assigns the affector to tiles using its scaled bounds.
+    let mut app = app();
+    spawn_affector(&mut app, transform, collider);
+    settle(&mut app);
+
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    assert_eq!(polygon_extents(&nav_mesh), (min, max));
+}
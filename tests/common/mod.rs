@@ -0,0 +1,132 @@
+//! Helpers shared by the integration tests. Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use oxidized_navigation::{
+    query::{find_path, FindPathError},
+    tiles::NavMeshTiles,
+    ActiveGenerationTasks, DirtyTiles, NavMesh, NavMeshAffector, NavMeshSettings,
+    OxidizedNavigationPlugin,
+};
+
+/// Settings similar to the ones used in the examples, tiles are 25 world units wide.
+pub fn settings() -> NavMeshSettings {
+    NavMeshSettings {
+        cell_width: 0.25,
+        cell_height: 0.1,
+        tile_width: 100,
+        world_half_extents: 250.0,
+        world_bottom_bound: -100.0,
+        up: Vec3::Y,
+        max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
+        walkable_height: 20,
+        walkable_radius: 1,
+        step_height: 3,
+        min_region_area: 100,
+        merge_region_area: 500,
+        max_contour_simplification_error: 1.1,
+        raw_contours: false,
+        max_vertices_per_polygon: 3,
+        max_edge_length: 80,
+        max_tile_generation_tasks: Some(9),
+        tile_batch_size: None,
+        experimental_incremental_voxelization: false,
+    }
+}
+
+pub fn app_with_settings(settings: NavMeshSettings) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(OxidizedNavigationPlugin::new(settings));
+    app
+}
+
+pub fn app() -> App {
+    app_with_settings(settings())
+}
+
+/// Spawns a [NavMeshAffector] without a transform hierarchy, [MinimalPlugins] doesn't propagate transforms.
+pub fn spawn_affector(app: &mut App, transform: Transform, collider: Collider) -> Entity {
+    app.world
+        .spawn((GlobalTransform::from(transform), collider, NavMeshAffector))
+        .id()
+}
+
+/// Runs the app until no tiles are dirty & no generation tasks are running.
+pub fn settle(app: &mut App) {
+    let start = Instant::now();
+    let mut idle_updates = 0;
+    // Require a few idle updates in a row so tasks finishing this frame get inserted & their removals are processed.
+    while idle_updates < 3 {
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "nav-mesh generation didn't settle"
+        );
+
+        app.update();
+        let idle = app.world.resource::<DirtyTiles>().is_empty()
+            && app.world.resource::<ActiveGenerationTasks>().is_empty();
+        idle_updates = if idle { idle_updates + 1 } else { 0 };
+
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+pub fn polygon_count(app: &App) -> usize {
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    nav_mesh
+        .get_tiles()
+        .values()
+        .map(|tile| tile.polygons.len())
+        .sum()
+}
+
+/// Vertices & polygon indices of every tile, sorted by tile coordinate so two nav-meshes can be compared.
+pub fn snapshot(app: &App) -> Vec<(UVec2, Vec<Vec3>, Vec<Vec<u32>>)> {
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    let mut tiles: Vec<_> = nav_mesh
+        .get_tiles()
+        .iter()
+        .map(|(coord, tile)| {
+            (
+                *coord,
+                tile.vertices.clone(),
+                tile.polygons
+                    .iter()
+                    .map(|polygon| polygon.indices.to_vec())
+                    .collect(),
+            )
+        })
+        .collect();
+    tiles.sort_by_key(|(coord, _, _)| (coord.x, coord.y));
+    tiles
+}
+
+/// Finds a path on the app's nav-mesh using the app's [NavMeshSettings].
+pub fn path(app: &App, start: Vec3, end: Vec3) -> Result<Vec<Vec3>, FindPathError> {
+    let settings = app.world.resource::<NavMeshSettings>();
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    find_path(&nav_mesh, settings, start, end, None, None)
+}
+
+/// Smallest & largest polygon vertex positions in ``tiles``.
+pub fn polygon_extents(tiles: &NavMeshTiles) -> (Vec3, Vec3) {
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    for tile in tiles.get_tiles().values() {
+        for polygon in &tile.polygons {
+            for index in &polygon.indices {
+                min = min.min(tile.vertices[*index as usize]);
+                max = max.max(tile.vertices[*index as usize]);
+            }
+        }
+    }
+
+    (min, max)
+}
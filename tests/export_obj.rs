@@ -0,0 +1,106 @@
+mod common;
+
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{generate_navmesh, NavMeshAreaType};
+
+#[test]
+fn export_obj_groups_faces_by_area() {
+    let mut settings = settings();
+    // Polygons with more than three vertices are fan-triangulated.
+    settings.max_vertices_per_polygon = 6;
+
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let plate = Collider::cuboid(3.0, 0.1, 3.0);
+    let tiles = generate_navmesh(
+        &settings,
+        [
+            (&floor, &GlobalTransform::IDENTITY, None, None),
+            (
+                &plate,
+                &GlobalTransform::from_translation(Vec3::new(4.0, 0.05, 4.0)),
+                Some(&NavMeshAreaType(Some(2))),
+                None,
+            ),
+        ],
+    );
+    let obj = tiles.export_obj(&settings);
+
+    let lines: Vec<Vec<&str>> = obj
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    let count = |keyword: &str| lines.iter().filter(|line| line[0] == keyword).count();
+
+    let tile_count = tiles.get_tiles().len();
+    let vertex_count: usize = tiles
+        .get_tiles()
+        .values()
+        .map(|tile| tile.vertices.len())
+        .sum();
+    let triangle_count: usize = tiles
+        .get_tiles()
+        .values()
+        .flat_map(|tile| tile.polygons.iter())
+        .map(|polygon| polygon.indices.len() - 2)
+        .sum();
+    let polygon_count: usize = tiles
+        .get_tiles()
+        .values()
+        .map(|tile| tile.polygons.len())
+        .sum();
+    assert!(triangle_count > polygon_count);
+
+    assert_eq!(count("o"), tile_count);
+    assert_eq!(count("v"), vertex_count);
+    assert_eq!(count("f"), triangle_count);
+    // Only keywords that don't need anything besides the OBJ itself.
+    assert!(lines
+        .iter()
+        .all(|line| ["o", "v", "g", "f"].contains(&line[0])));
+
+    // A group starts whenever the area changes within a tile.
+    let group_count: usize = tiles
+        .get_tiles()
+        .values()
+        .map(|tile| {
+            1 + tile
+                .polygons
+                .windows(2)
+                .filter(|polygons| polygons[0].area != polygons[1].area)
+                .count()
+        })
+        .sum();
+    assert_eq!(count("g"), group_count);
+
+    let groups: BTreeSet<_> = lines
+        .iter()
+        .filter(|line| line[0] == "g")
+        .map(|line| line[1])
+        .collect();
+    assert_eq!(groups, BTreeSet::from(["area_0", "area_2"]));
+
+    // Faces only reference vertices of their own tile.
+    let mut tile_vertices = 1..1;
+    for line in &lines {
+        match line[0] {
+            "o" => tile_vertices = tile_vertices.end..tile_vertices.end,
+            "v" => tile_vertices.end += 1,
+            "f" => {
+                assert_eq!(line.len(), 4);
+                for index in &line[1..] {
+                    let index: usize = index.parse().unwrap();
+                    assert!(
+                        tile_vertices.contains(&index),
+                        "{index} not in {tile_vertices:?}"
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(tile_vertices.end, vertex_count + 1);
+}
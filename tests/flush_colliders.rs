@@ -0,0 +1,104 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{generate_navmesh, query::find_path, tiles::NavMeshTiles};
+
+const START: Vec3 = Vec3::new(-8.0, 0.1, 0.0);
+
+/// Whether a path from [START] reaches ``end``.
+fn reaches(tiles: &NavMeshTiles, end: Vec3) -> bool {
+    find_path(tiles, &settings(), START, end, Some(0.5), None)
+        .is_ok_and(|path| path.last().unwrap().distance(end) < 0.5)
+}
+
+/// Two 10x10 cuboids side by side, the second ``gap`` further along X & ``height_difference`` higher.
+fn cuboids(height_difference: f32, gap: f32) -> NavMeshTiles {
+    let collider = Collider::cuboid(5.0, 0.1, 5.0);
+    let first = GlobalTransform::from_xyz(-5.0, 0.0, 0.3);
+    let second = GlobalTransform::from_xyz(5.0 + gap, height_difference, 0.3);
+
+    generate_navmesh(
+        &settings(),
+        [
+            (&collider, &first, None, None),
+            (&collider, &second, None, None),
+        ],
+    )
+}
+
+#[test]
+fn crosses_flush_cuboids() {
+    // The first cuboid's side reaches up to a voxel above the second's top, leaving a seam unless the spans are merged.
+    for height_difference in [0.0, -0.01, -0.05, -0.09, 0.05, 0.09] {
+        let end = Vec3::new(8.0, 0.1 + height_difference, 0.0);
+        assert!(
+            reaches(&cuboids(height_difference, 0.0), end),
+            "{height_difference}"
+        );
+    }
+
+    // More than a voxel apart the side is still a wall, as is an actual gap.
+    assert!(!reaches(&cuboids(-0.2, 0.0), Vec3::new(8.0, -0.1, 0.0)));
+    assert!(!reaches(&cuboids(0.0, 1.0), Vec3::new(9.0, 0.1, 0.0)));
+}
+
+fn quad(min: Vec3, max: Vec3) -> (Vec<Vec3>, Vec<[u32; 3]>) {
+    (
+        vec![
+            min,
+            Vec3::new(max.x, max.y, min.z),
+            max,
+            Vec3::new(min.x, min.y, max.z),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    )
+}
+
+/// A steep, narrow ridge along Z across the whole floor, its top less than a voxel above the floor.
+fn ridge() -> [(Vec<Vec3>, Vec<[u32; 3]>); 2] {
+    [
+        quad(Vec3::new(0.06, 0.05, -5.0), Vec3::new(0.11, 0.12, 5.0)),
+        quad(Vec3::new(0.11, 0.12, -5.0), Vec3::new(0.16, 0.05, 5.0)),
+    ]
+}
+
+fn trimesh(quads: impl IntoIterator<Item = (Vec<Vec3>, Vec<[u32; 3]>)>) -> Collider {
+    let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+    for (quad_vertices, quad_indices) in quads {
+        let offset = vertices.len() as u32;
+        vertices.extend(quad_vertices);
+        indices.extend(
+            quad_indices
+                .iter()
+                .map(|triangle| triangle.map(|index| index + offset)),
+        );
+    }
+
+    Collider::trimesh(vertices, indices)
+}
+
+#[test]
+fn steep_faces_of_one_affector_are_not_merged() {
+    let floor = quad(Vec3::new(-10.0, 0.05, -5.0), Vec3::new(10.0, 0.05, 5.0));
+    let end = Vec3::new(8.0, 0.1, 0.0);
+    let transform = GlobalTransform::IDENTITY;
+
+    // Part of the same affector as the floor, the ridge is a wall.
+    let collider = trimesh(ridge().into_iter().chain([floor.clone()]));
+    let tiles = generate_navmesh(&settings(), [(&collider, &transform, None, None)]);
+    assert!(reaches(&tiles, Vec3::new(-3.0, 0.1, 0.0)));
+    assert!(!reaches(&tiles, end));
+
+    // As a separate affector the ridge is the floor's surface.
+    let (floor, ridge) = (trimesh([floor]), trimesh(ridge()));
+    let tiles = generate_navmesh(
+        &settings(),
+        [
+            (&floor, &transform, None, None),
+            (&ridge, &transform, None, None),
+        ],
+    );
+    assert!(reaches(&tiles, end));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::collections::HashSet;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{ActiveGenerationTasks, DirtyTiles, GenerationTicker, NavMesh};
+
+#[test]
+fn accessors_report_tiles_until_generated() {
+    let mut settings = settings();
+    // Only one tile is generated at a time, the other has to wait in the dirty tiles.
+    settings.max_tile_generation_tasks = Some(1);
+    let mut app = app_with_settings(settings.clone());
+
+    let positions = [Vec3::new(60.0, 0.0, 60.0), Vec3::new(-60.0, 0.0, -60.0)];
+    let tiles: HashSet<_> = positions
+        .iter()
+        .map(|position| settings.get_tile_containing_position(position.xz()))
+        .collect();
+    for position in positions {
+        spawn_affector(
+            &mut app,
+            Transform::from_translation(position),
+            Collider::cuboid(2.0, 0.1, 2.0),
+        );
+    }
+    app.update();
+
+    // Finished tasks are only removed on the next update, so the task is still listed.
+    let active_generation_tasks = app.world.resource::<ActiveGenerationTasks>();
+    assert_eq!(active_generation_tasks.len(), 1);
+    let generating: Vec<_> = active_generation_tasks.get_tiles().collect();
+    assert_eq!(generating.len(), 1);
+
+    let dirty_tiles = app.world.resource::<DirtyTiles>();
+    assert_eq!(dirty_tiles.len(), 1);
+    let waiting: Vec<_> = dirty_tiles.get_tiles().collect();
+    assert!(!dirty_tiles.contains(generating[0]));
+    assert_eq!(
+        HashSet::from([generating[0], waiting[0]]),
+        tiles,
+        "every tile is either generating or waiting"
+    );
+    assert_eq!(app.world.resource::<GenerationTicker>().get(), 1);
+
+    settle(&mut app);
+    assert!(app.world.resource::<DirtyTiles>().is_empty());
+    assert!(app.world.resource::<ActiveGenerationTasks>().is_empty());
+    assert_eq!(app.world.resource::<GenerationTicker>().get(), 2);
+
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    assert_eq!(nav_mesh.get_tile_generation(generating[0]), Some(1));
+    assert_eq!(nav_mesh.get_tile_generation(waiting[0]), Some(2));
+}
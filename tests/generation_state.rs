@@ -0,0 +1,68 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    ActiveGenerationTasks, DirtyTiles, GenerationTicker, NavMesh, NavMeshGenerationState,
+};
+
+/// Runs a number of updates, giving any tasks that were spawned by mistake time to finish.
+fn update_paused(app: &mut App) {
+    for _ in 0..10 {
+        app.update();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn generated_tiles(app: &App) -> Vec<UVec2> {
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    nav_mesh.get_tiles().keys().copied().collect()
+}
+
+#[test]
+fn paused_generation_keeps_dirty_tiles_until_resumed() {
+    let mut app = app();
+    app.insert_resource(NavMeshGenerationState::Paused);
+
+    let position = Vec3::new(60.0, 0.0, 60.0);
+    let tile = settings().get_tile_containing_position(position.xz());
+    let affector = spawn_affector(
+        &mut app,
+        Transform::from_translation(position),
+        Collider::cuboid(2.0, 0.1, 2.0),
+    );
+    update_paused(&mut app);
+
+    assert!(app.world.resource::<DirtyTiles>().contains(tile));
+    assert!(app.world.resource::<ActiveGenerationTasks>().is_empty());
+    assert_eq!(app.world.resource::<GenerationTicker>().get(), 0);
+    assert!(generated_tiles(&app).is_empty());
+
+    app.insert_resource(NavMeshGenerationState::Running);
+    settle(&mut app);
+    assert_eq!(generated_tiles(&app), [tile]);
+
+    // Moving the affector while paused leaves the nav-mesh as it was.
+    app.insert_resource(NavMeshGenerationState::Paused);
+    let generation = app.world.resource::<GenerationTicker>().get();
+    let new_position = Vec3::new(-60.0, 0.0, -60.0);
+    let new_tile = settings().get_tile_containing_position(new_position.xz());
+    app.world
+        .entity_mut(affector)
+        .insert(GlobalTransform::from_translation(new_position));
+    update_paused(&mut app);
+
+    let dirty_tiles = app.world.resource::<DirtyTiles>();
+    assert!(dirty_tiles.contains(tile));
+    assert!(dirty_tiles.contains(new_tile));
+    assert_eq!(app.world.resource::<GenerationTicker>().get(), generation);
+    assert_eq!(generated_tiles(&app), [tile]);
+
+    app.insert_resource(NavMeshGenerationState::Running);
+    settle(&mut app);
+    assert_eq!(generated_tiles(&app), [new_tile]);
+}
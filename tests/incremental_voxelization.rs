@@ -0,0 +1,63 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+
+fn spawn_floor(app: &mut App) {
+    spawn_affector(app, Transform::IDENTITY, Collider::cuboid(20.0, 0.1, 20.0));
+}
+
+fn full_rebuild(box_position: Option<Vec3>) -> App {
+    let mut app = app();
+    spawn_floor(&mut app);
+    if let Some(position) = box_position {
+        spawn_affector(
+            &mut app,
+            Transform::from_translation(position),
+            Collider::cuboid(1.0, 0.5, 1.0),
+        );
+    }
+    settle(&mut app);
+    app
+}
+
+#[test]
+fn incremental_rebuild_matches_full_rebuild() {
+    let mut settings = settings();
+    settings.experimental_incremental_voxelization = true;
+    let mut app = app_with_settings(settings);
+
+    spawn_floor(&mut app);
+    let start = Vec3::new(3.0, 0.5, 3.0);
+    let moving_box = spawn_affector(
+        &mut app,
+        Transform::from_translation(start),
+        Collider::cuboid(1.0, 0.5, 1.0),
+    );
+    settle(&mut app);
+    assert!(polygon_count(&app) > 0);
+    assert_eq!(snapshot(&app), snapshot(&full_rebuild(Some(start))));
+
+    // The last position is in a different tile than the others.
+    for position in [
+        Vec3::new(5.0, 0.5, 2.0),
+        Vec3::new(-4.0, 0.5, 7.5),
+        Vec3::new(12.3, 0.5, -8.1),
+    ] {
+        app.world
+            .entity_mut(moving_box)
+            .insert(GlobalTransform::from_translation(position));
+        settle(&mut app);
+
+        assert_eq!(
+            snapshot(&app),
+            snapshot(&full_rebuild(Some(position))),
+            "box moved to {position}"
+        );
+    }
+
+    app.world.despawn(moving_box);
+    settle(&mut app);
+    assert_eq!(snapshot(&app), snapshot(&full_rebuild(None)));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::{
+    fmt::{Debug, Write},
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    prelude::*,
+    utils::tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        subscriber::{self, Subscriber},
+        Event, Level, Metadata,
+    },
+};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::generate_navmesh;
+
+/// Collects the messages of warnings & errors logged while it's the default subscriber.
+#[derive(Clone, Default)]
+struct WarningCollector(Arc<Mutex<Vec<String>>>);
+
+impl Subscriber for WarningCollector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= Level::WARN
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        struct Message<'a>(&'a mut String);
+        impl Visit for Message<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                if field.name() == "message" {
+                    let _ = write!(self.0, "{value:?}");
+                }
+            }
+        }
+
+        let mut message = String::new();
+        event.record(&mut Message(&mut message));
+        self.0.lock().unwrap().push(message);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn geometry_above_max_representable_height_is_clamped() {
+    let mut settings = settings();
+    // Everything more than 65.535 units above the bottom bound is out of range.
+    settings.cell_height = 0.001;
+    settings.world_bottom_bound = -1.0;
+    let max_height = settings.max_representable_height();
+    assert!(max_height < 70.0);
+
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let platform = Collider::cuboid(3.0, 0.5, 3.0);
+    let platform_transform = GlobalTransform::from_translation(Vec3::new(5.0, 80.0, 5.0));
+
+    let warnings = WarningCollector::default();
+    let tiles = subscriber::with_default(warnings.clone(), || {
+        generate_navmesh(
+            &settings,
+            [
+                (&floor, &GlobalTransform::IDENTITY, None, None),
+                (&platform, &platform_transform, None, None),
+            ],
+        )
+    });
+
+    let warnings = warnings.0.lock().unwrap();
+    assert!(
+        warnings
+            .iter()
+            .any(|warning| warning.contains("above the maximum representable height")),
+        "{warnings:?}"
+    );
+
+    // Wrapping around would put the platform somewhere between the floor & the maximum height.
+    let mut floor_vertices = 0;
+    for tile in tiles.get_tiles().values() {
+        for polygon in &tile.polygons {
+            for index in &polygon.indices {
+                let y = tile.vertices[*index as usize].y;
+                assert!(
+                    y < 0.5 || (y - max_height).abs() < 0.01,
+                    "{y} is neither on the floor nor at {max_height}"
+                );
+                floor_vertices += usize::from(y < 0.5);
+            }
+        }
+    }
+    assert!(floor_vertices > 0);
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    query::{find_path_with_options, FindPathOptions},
+    tiles::Link,
+    NavMesh, NavMeshAreaType, NavMeshSettings,
+};
+
+/// Height of the segment ``a``-``b`` at the point closest to ``position`` on the XZ-plane.
+fn height_on_segment(a: Vec3, b: Vec3, position: Vec3) -> f32 {
+    let ab = b.xz() - a.xz();
+    let t = ((position.xz() - a.xz()).dot(ab) / ab.length_squared()).clamp(0.0, 1.0);
+
+    a.lerp(b, t).y
+}
+
+#[test]
+fn external_vertical_delta_is_largest_height_difference_along_portal() {
+    let mut app = app();
+    // A tilted ramp crossing the borders between four tiles, the tiles split their border edges at different points.
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(0.013, 1.0, 0.37)
+            .with_rotation(Quat::from_rotation_y(0.5) * Quat::from_rotation_z(0.25)),
+        Collider::cuboid(8.0, 0.1, 8.0),
+    );
+    settle(&mut app);
+
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+
+    let mut largest_delta: f32 = 0.0;
+    for (coord, tile) in nav_mesh.get_tiles() {
+        for polygon in &tile.polygons {
+            // Polygons within a tile share their vertices, internal links get their delta from the voxelized spans instead.
+            for link in polygon
+                .links
+                .iter()
+                .filter(|link| matches!(link, Link::External { .. }))
+            {
+                let (a, b) = tile.get_link_portal(polygon, link);
+                let (neighbour_coord, neighbour_polygon) = link.get_neighbour(*coord);
+                let neighbour_tile = &nav_mesh.get_tiles()[&neighbour_coord];
+                let neighbour_polygon = &neighbour_tile.polygons[neighbour_polygon as usize];
+
+                // The neighbour's edge lying on the same line as the portal.
+                let indices = &neighbour_polygon.indices;
+                let (c, d) = (0..indices.len())
+                    .map(|i| {
+                        (
+                            neighbour_tile.vertices[indices[i] as usize],
+                            neighbour_tile.vertices[indices[(i + 1) % indices.len()] as usize],
+                        )
+                    })
+                    .find(|(c, d)| {
+                        let direction = (b.xz() - a.xz()).normalize();
+                        [*c, *d]
+                            .iter()
+                            .all(|vertex| direction.perp_dot(vertex.xz() - a.xz()).abs() < 0.01)
+                    })
+                    .expect("linked polygon doesn't share the portal's edge");
+
+                let expected = (a.y - height_on_segment(c, d, a))
+                    .abs()
+                    .max((b.y - height_on_segment(c, d, b)).abs());
+                assert!(
+                    (link.vertical_delta() - expected).abs() < 0.02,
+                    "{} != {expected}",
+                    link.vertical_delta()
+                );
+
+                largest_delta = largest_delta.max(link.vertical_delta());
+            }
+        }
+    }
+
+    assert!(largest_delta > 0.02);
+}
+
+/// Returns true if the path from ``start`` reaches ``end``, a blocked path ends at the polygon closest to ``end`` instead.
+fn reaches_with_max_step(app: &App, start: Vec3, end: Vec3, max_step: Option<f32>) -> bool {
+    let settings = app.world.resource::<NavMeshSettings>();
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().unwrap();
+    let options = FindPathOptions {
+        max_step,
+        ..Default::default()
+    };
+
+    let path = find_path_with_options(&nav_mesh, settings, start, end, &options).unwrap();
+    path.last().unwrap().distance(end) < 0.3
+}
+
+#[test]
+fn max_step_constrains_steps_within_a_tile() {
+    let settings = settings();
+    let step = settings.step_height as f32 * settings.cell_height;
+
+    let mut app = app_with_settings(settings);
+    // Everything is inside the tile spanning 0.0 to 25.0 on both axes.
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(12.5, -0.1, 12.5),
+        Collider::cuboid(10.0, 0.1, 10.0),
+    );
+    // A different area type splits the step into its own region so polygon edges follow it, otherwise it's smoothed over inside the polygons.
+    let step_affector = spawn_affector(
+        &mut app,
+        Transform::from_xyz(17.5, step / 2.0, 12.5),
+        Collider::cuboid(3.0, step / 2.0, 3.0),
+    );
+    app.world
+        .entity_mut(step_affector)
+        .insert(NavMeshAreaType(Some(1)));
+    settle(&mut app);
+
+    let low = Vec3::new(6.0, 0.0, 12.5);
+    let high = Vec3::new(17.5, step, 12.5);
+    let other_low = Vec3::new(12.5, 0.0, 5.0);
+
+    assert!(reaches_with_max_step(&app, low, high, None));
+    assert!(reaches_with_max_step(&app, low, high, Some(step)));
+    assert!(!reaches_with_max_step(&app, low, high, Some(step / 2.0)));
+    // Flat ground next to the step is still traversable.
+    assert!(reaches_with_max_step(
+        &app,
+        low,
+        other_low,
+        Some(step / 2.0)
+    ));
+}
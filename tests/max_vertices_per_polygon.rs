@@ -0,0 +1,96 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::NavMesh;
+
+/// A floor with obstacles at different angles, so merging has to produce polygons of varying sizes.
+fn spawn_scene(app: &mut App) {
+    spawn_affector(app, Transform::IDENTITY, Collider::cuboid(20.0, 0.1, 20.0));
+    spawn_affector(
+        app,
+        Transform::from_xyz(3.0, 0.5, 3.0),
+        Collider::cuboid(1.0, 0.5, 1.0),
+    );
+    spawn_affector(
+        app,
+        Transform::from_xyz(-6.0, 0.5, -6.0).with_rotation(Quat::from_rotation_y(0.6)),
+        Collider::cylinder(0.5, 2.0),
+    );
+    spawn_affector(
+        app,
+        Transform::from_xyz(8.0, 0.0, -8.0).with_rotation(Quat::from_rotation_z(0.3)),
+        Collider::cuboid(3.0, 0.1, 3.0),
+    );
+}
+
+#[test]
+fn hexagons_are_convex_and_pathable() {
+    let mut settings = settings();
+    settings.max_vertices_per_polygon = 6;
+    let mut app = app_with_settings(settings);
+    spawn_scene(&mut app);
+    settle(&mut app);
+
+    let mut triangles = app_with_settings(common::settings());
+    spawn_scene(&mut triangles);
+    settle(&mut triangles);
+    assert!(polygon_count(&app) < polygon_count(&triangles));
+
+    {
+        let nav_mesh = app.world.resource::<NavMesh>().get();
+        let nav_mesh = nav_mesh.read().unwrap();
+
+        let mut hexagons = 0;
+        for tile in nav_mesh.get_tiles().values() {
+            for polygon in &tile.polygons {
+                let vertices: Vec<Vec3> = polygon
+                    .indices
+                    .iter()
+                    .map(|index| tile.vertices[*index as usize])
+                    .collect();
+                assert!((3..=6).contains(&vertices.len()));
+                if vertices.len() == 6 {
+                    hexagons += 1;
+                }
+
+                // Every corner turns the same way.
+                let turns: Vec<f32> = (0..vertices.len())
+                    .map(|i| {
+                        let a = vertices[i];
+                        let b = vertices[(i + 1) % vertices.len()];
+                        let c = vertices[(i + 2) % vertices.len()];
+
+                        (b.x - a.x) * (c.z - a.z) - (c.x - a.x) * (b.z - a.z)
+                    })
+                    .collect();
+                assert!(
+                    turns.iter().all(|turn| *turn > 0.0) || turns.iter().all(|turn| *turn < 0.0),
+                    "polygon isn't convex: {vertices:?}"
+                );
+            }
+        }
+        assert!(hexagons > 0);
+    }
+
+    for (start, end) in [
+        // Across several tiles & around the box.
+        (Vec3::new(-15.0, 0.1, -15.0), Vec3::new(15.0, 0.1, 15.0)),
+        (Vec3::new(3.0, 0.1, -1.0), Vec3::new(3.0, 0.1, 7.0)),
+    ] {
+        let path = path(&app, start, end).unwrap();
+        assert!(path.last().unwrap().distance(end) < 0.5, "{path:?}");
+
+        // The string pulled path doesn't cut through the box.
+        for segment in path.windows(2) {
+            for t in [0.25, 0.5, 0.75] {
+                let point = segment[0].lerp(segment[1], t);
+                assert!(
+                    (point.x - 3.0).abs() > 1.0 || (point.z - 3.0).abs() > 1.0,
+                    "{path:?}"
+                );
+            }
+        }
+    }
+}
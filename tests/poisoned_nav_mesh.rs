@@ -0,0 +1,43 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::NavMesh;
+
+#[test]
+fn generation_recovers_poisoned_nav_mesh() {
+    let mut app = app();
+    spawn_affector(
+        &mut app,
+        Transform::IDENTITY,
+        Collider::cuboid(20.0, 0.1, 20.0),
+    );
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(3.0, 0.5, 3.0),
+        Collider::cuboid(1.0, 0.5, 1.0),
+    );
+    settle(&mut app);
+    let before = snapshot(&app);
+    assert!(!before.is_empty());
+
+    let nav_mesh = app.world.resource::<NavMesh>().get();
+    let _ = std::thread::spawn(move || {
+        let _guard = nav_mesh.write().unwrap();
+        panic!("poisoning the nav-mesh lock");
+    })
+    .join();
+    assert!(app.world.resource::<NavMesh>().is_poisoned());
+
+    settle(&mut app);
+
+    assert!(!app.world.resource::<NavMesh>().is_poisoned());
+    assert_eq!(snapshot(&app), before);
+    assert!(path(
+        &app,
+        Vec3::new(-10.0, 0.1, -10.0),
+        Vec3::new(10.0, 0.1, 10.0)
+    )
+    .is_ok());
+}
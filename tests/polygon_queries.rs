@@ -0,0 +1,140 @@
+mod common;
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{polygons_in_aabb, polygons_in_sphere},
+    tiles::{NavMeshTiles, PolygonRef},
+};
+
+/// A floor centered on the corner between tiles ``(9, 9)``, ``(10, 9)``, ``(9, 10)`` & ``(10, 10)``.
+fn floor_across_tile_corner() -> NavMeshTiles {
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+
+    generate_navmesh(
+        &settings(),
+        [(&floor, &GlobalTransform::IDENTITY, None, None)],
+    )
+}
+
+/// Every polygon of ``tiles`` whose bounds ``overlaps`` returns true for, checked without going through the queries.
+fn brute_force(tiles: &NavMeshTiles, overlaps: impl Fn(Vec3, Vec3) -> bool) -> HashSet<PolygonRef> {
+    tiles
+        .get_tiles()
+        .iter()
+        .flat_map(|(coord, tile)| {
+            tile.polygons
+                .iter()
+                .enumerate()
+                .map(move |(index, polygon)| ((*coord, index as u16), tile, polygon))
+        })
+        .filter(|(_, tile, polygon)| {
+            let vertices = polygon
+                .indices
+                .iter()
+                .map(|index| tile.vertices[*index as usize]);
+            let min = vertices.clone().fold(Vec3::splat(f32::MAX), Vec3::min);
+            let max = vertices.fold(Vec3::splat(f32::MIN), Vec3::max);
+
+            overlaps(min, max)
+        })
+        .map(|(polygon_ref, _, _)| polygon_ref)
+        .collect()
+}
+
+fn tiles_of(polygons: &[PolygonRef]) -> HashSet<UVec2> {
+    polygons.iter().map(|(tile, _)| *tile).collect()
+}
+
+#[test]
+fn aabb_includes_polygons_up_to_tile_borders() {
+    let settings = settings();
+    let tiles = floor_across_tile_corner();
+
+    let aabb = |min: Vec3, max: Vec3| {
+        let polygons = polygons_in_aabb(&tiles, &settings, min, max);
+        let expected = brute_force(&tiles, |polygon_min, polygon_max| {
+            polygon_min.cmple(max).all() && polygon_max.cmpge(min).all()
+        });
+        assert_eq!(polygons.iter().copied().collect::<HashSet<_>>(), expected);
+        assert_eq!(polygons.len(), expected.len(), "polygons returned twice");
+
+        polygons
+    };
+
+    // Just inside tile (10, 10), the polygons of (9, 10) end at the border.
+    let polygons = aabb(Vec3::new(0.5, -1.0, 0.5), Vec3::new(5.0, 1.0, 5.0));
+    assert_eq!(tiles_of(&polygons), HashSet::from([UVec2::new(10, 10)]));
+    // Just inside tile (9, 10).
+    let polygons = aabb(Vec3::new(-5.0, -1.0, 0.5), Vec3::new(-0.5, 1.0, 5.0));
+    assert_eq!(tiles_of(&polygons), HashSet::from([UVec2::new(9, 10)]));
+    // Touching the border between the two includes both.
+    let polygons = aabb(Vec3::new(0.0, -1.0, 0.5), Vec3::new(5.0, 1.0, 5.0));
+    assert_eq!(
+        tiles_of(&polygons),
+        HashSet::from([UVec2::new(9, 10), UVec2::new(10, 10)])
+    );
+    // Across the corner.
+    let polygons = aabb(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    assert_eq!(tiles_of(&polygons).len(), 4);
+
+    // Past the floor's edge & above it.
+    assert!(aabb(Vec3::new(12.0, -1.0, -5.0), Vec3::new(20.0, 1.0, 5.0)).is_empty());
+    assert!(aabb(Vec3::new(-5.0, 2.0, -5.0), Vec3::new(5.0, 3.0, 5.0)).is_empty());
+}
+
+#[test]
+fn sphere_includes_polygons_up_to_tile_borders() {
+    let settings = settings();
+    let tiles = floor_across_tile_corner();
+
+    let sphere = |center: Vec3, radius: f32| {
+        let polygons = polygons_in_sphere(&tiles, &settings, center, radius);
+        let expected = brute_force(&tiles, |polygon_min, polygon_max| {
+            center.clamp(polygon_min, polygon_max).distance(center) <= radius
+        });
+        assert_eq!(polygons.iter().copied().collect::<HashSet<_>>(), expected);
+        assert_eq!(polygons.len(), expected.len(), "polygons returned twice");
+
+        polygons
+    };
+
+    // Close to the border but not reaching it.
+    let polygons = sphere(Vec3::new(-0.5, 0.1, 3.0), 0.3);
+    assert_eq!(tiles_of(&polygons), HashSet::from([UVec2::new(9, 10)]));
+    // Reaching over the border.
+    let polygons = sphere(Vec3::new(-0.5, 0.1, 3.0), 0.7);
+    assert_eq!(
+        tiles_of(&polygons),
+        HashSet::from([UVec2::new(9, 10), UVec2::new(10, 10)])
+    );
+    assert_eq!(tiles_of(&sphere(Vec3::new(0.0, 0.1, 0.0), 1.0)).len(), 4);
+
+    assert!(sphere(Vec3::new(15.0, 0.1, 0.0), 3.0).is_empty());
+}
+
+#[test]
+fn oversized_queries_return_every_polygon() {
+    let settings = settings();
+    let tiles = floor_across_tile_corner();
+    let polygon_count: usize = tiles
+        .get_tiles()
+        .values()
+        .map(|tile| tile.polygons.len())
+        .sum();
+
+    let polygons = polygons_in_sphere(&tiles, &settings, Vec3::ZERO, f32::MAX);
+    assert_eq!(polygons.len(), polygon_count);
+
+    let polygons = polygons_in_aabb(
+        &tiles,
+        &settings,
+        Vec3::splat(f32::MIN),
+        Vec3::splat(f32::MAX),
+    );
+    assert_eq!(polygons.len(), polygon_count);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{generate_navmesh, query::find_path, NavMeshSettings};
+
+/// Total vertex count of a floor with a cylinder standing on it, the cylinder's curved wall produces a jagged voxel contour.
+fn curved_wall_vertex_count(settings: &NavMeshSettings) -> usize {
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let cylinder = Collider::cylinder(1.0, 4.0);
+    let tiles = generate_navmesh(
+        settings,
+        [
+            (&floor, &GlobalTransform::IDENTITY, None, None),
+            (
+                &cylinder,
+                &GlobalTransform::from_translation(Vec3::new(2.0, 1.0, 2.0)),
+                None,
+                None,
+            ),
+        ],
+    );
+
+    tiles
+        .get_tiles()
+        .values()
+        .map(|tile| tile.vertices.len())
+        .sum()
+}
+
+#[test]
+fn raw_contours_keep_more_vertices_than_simplified() {
+    let mut settings = settings();
+    let simplified = curved_wall_vertex_count(&settings);
+
+    settings.max_contour_simplification_error = 0.0;
+    let zero_error = curved_wall_vertex_count(&settings);
+    // Negative errors behave like zero.
+    settings.max_contour_simplification_error = -1.0;
+    assert_eq!(curved_wall_vertex_count(&settings), zero_error);
+
+    settings.raw_contours = true;
+    let raw = curved_wall_vertex_count(&settings);
+
+    assert!(simplified < zero_error, "{simplified} {zero_error}");
+    assert!(zero_error <= raw, "{zero_error} {raw}");
+}
+
+#[test]
+fn raw_contours_are_pathable() {
+    let mut settings = settings();
+    settings.raw_contours = true;
+
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let tiles = generate_navmesh(
+        &settings,
+        [(&floor, &GlobalTransform::IDENTITY, None, None)],
+    );
+
+    let end = Vec3::new(5.0, 0.1, 5.2);
+    let path = find_path(
+        &tiles,
+        &settings,
+        Vec3::new(-5.0, 0.1, -5.3),
+        end,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(path.last().unwrap().distance(end) < 0.5, "{path:?}");
+}
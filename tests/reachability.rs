@@ -0,0 +1,72 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_path, is_reachable},
+    tiles::NavMeshTiles,
+};
+
+/// A floor across the corner of four tiles & a separate island in the next tile over.
+fn two_islands() -> NavMeshTiles {
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let island = Collider::cuboid(5.0, 0.1, 5.0);
+
+    generate_navmesh(
+        &settings(),
+        [
+            (&floor, &GlobalTransform::IDENTITY, None, None),
+            (
+                &island,
+                &GlobalTransform::from_translation(Vec3::new(40.0, 0.0, 5.0)),
+                None,
+                None,
+            ),
+        ],
+    )
+}
+
+#[test]
+fn reachable_across_tiles_but_not_between_islands() {
+    let settings = settings();
+    let tiles = two_islands();
+
+    let cases = [
+        // Diagonally across the four tiles of the floor.
+        (Vec3::new(-8.0, 0.1, -8.0), Vec3::new(8.0, 0.1, 8.0), true),
+        (Vec3::new(8.0, 0.1, -8.0), Vec3::new(-8.0, 0.1, 8.0), true),
+        (Vec3::new(38.0, 0.1, 3.0), Vec3::new(42.0, 0.1, 7.0), true),
+        // From the floor to the island & back.
+        (Vec3::new(8.0, 0.1, 5.0), Vec3::new(38.0, 0.1, 5.0), false),
+        (Vec3::new(42.0, 0.1, 7.0), Vec3::new(-8.0, 0.1, -8.0), false),
+    ];
+
+    for (start, end, reachable) in cases {
+        assert_eq!(
+            is_reachable(&tiles, &settings, start, end, None, None).unwrap(),
+            reachable,
+            "{start} to {end}"
+        );
+
+        // An unreachable path ends on the polygon closest to the end instead.
+        let path = find_path(&tiles, &settings, start, end, None, None).unwrap();
+        assert_eq!(
+            path.last().unwrap().distance(end) < 0.3,
+            reachable,
+            "{start} to {end}"
+        );
+    }
+}
+
+#[test]
+fn off_mesh_positions_fail_like_find_path() {
+    let settings = settings();
+    let tiles = two_islands();
+
+    let start = Vec3::new(0.0, 0.1, 0.0);
+    let end = Vec3::new(100.0, 0.1, 100.0);
+    assert!(is_reachable(&tiles, &settings, start, end, None, None).is_err());
+    assert!(find_path(&tiles, &settings, start, end, None, None).is_err());
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+
+#[test]
+fn step_is_traversable_both_ways() {
+    let settings = settings();
+    // Exactly step_height, the largest step that's traversable.
+    let step = settings.step_height as f32 * settings.cell_height;
+
+    let mut app = app_with_settings(settings);
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(0.0, -0.1, 0.0),
+        Collider::cuboid(10.0, 0.1, 10.0),
+    );
+    spawn_affector(
+        &mut app,
+        Transform::from_xyz(5.0, step / 2.0, 0.0),
+        Collider::cuboid(3.0, step / 2.0, 3.0),
+    );
+    settle(&mut app);
+
+    let (low, high) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, step, 0.0));
+    let up = path(&app, low, high).unwrap();
+    let down = path(&app, high, low).unwrap();
+
+    assert!(up.last().unwrap().distance(high) < 0.3, "{up:?}");
+    assert!(down.last().unwrap().distance(low) < 0.3, "{down:?}");
+}
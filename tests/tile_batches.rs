@@ -0,0 +1,146 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::NavMeshSettings;
+
+/// Geometry crossing many tile borders, including rotated & sloped colliders whose edges don't line up with cells.
+fn spawn_scene(app: &mut App) -> Entity {
+    spawn_affector(
+        app,
+        Transform::from_xyz(10.0, 0.0, 30.0).with_rotation(Quat::from_rotation_x(0.2)),
+        Collider::cuboid(60.0, 0.1, 25.0),
+    );
+    spawn_affector(app, Transform::IDENTITY, Collider::cuboid(20.0, 0.1, 20.0));
+    spawn_affector(
+        app,
+        Transform::from_xyz(-6.0, 0.5, -6.0).with_rotation(Quat::from_rotation_y(0.6)),
+        Collider::cylinder(0.5, 2.0),
+    );
+    spawn_affector(
+        app,
+        Transform::from_xyz(0.013, 0.0, -24.96).with_rotation(Quat::from_rotation_z(0.3)),
+        Collider::cuboid(3.0, 0.1, 3.0),
+    );
+    spawn_affector(
+        app,
+        Transform::from_xyz(3.0, 0.5, 3.0),
+        Collider::cuboid(1.0, 0.5, 1.0),
+    )
+}
+
+#[test]
+fn batched_generation_matches_individual_generation() {
+    for (tile_batch_size, experimental_incremental_voxelization) in [(3, false), (2, true)] {
+        let mut settings = settings();
+        settings.tile_batch_size = Some(tile_batch_size);
+        settings.experimental_incremental_voxelization = experimental_incremental_voxelization;
+
+        let mut batched = app_with_settings(settings);
+        let mut individual = app();
+        let boxes = [&mut batched, &mut individual].map(spawn_scene);
+        settle(&mut batched);
+        settle(&mut individual);
+
+        let (batched_tiles, individual_tiles) = (snapshot(&batched), snapshot(&individual));
+        assert!(batched_tiles.len() > 9);
+        assert_eq!(batched_tiles.len(), individual_tiles.len());
+        for (batched_tile, individual_tile) in batched_tiles.iter().zip(individual_tiles.iter()) {
+            assert_eq!(batched_tile, individual_tile, "tile {}", batched_tile.0);
+        }
+
+        // Move the box next to the corner between four tiles.
+        for (app, moving_box) in [&mut batched, &mut individual].into_iter().zip(boxes) {
+            app.world
+                .entity_mut(moving_box)
+                .insert(GlobalTransform::from_translation(Vec3::new(
+                    24.0, 0.5, 24.5,
+                )));
+            settle(app);
+        }
+        assert_eq!(snapshot(&batched), snapshot(&individual));
+    }
+}
+
+#[test]
+fn batched_voxelization_matches_individual_voxelization() {
+    // The plate's spans merge with the floor's, a block gathers its affectors in a different order than a single tile would.
+    let spawn_scene = |app: &mut App| {
+        spawn_affector(
+            app,
+            Transform::from_xyz(12.5, 0.0, 12.5),
+            Collider::cuboid(50.0, 0.1, 50.0),
+        );
+        spawn_affector(
+            app,
+            Transform::from_xyz(31.5, 0.6, 21.2).with_rotation(Quat::from_rotation_y(0.2)),
+            Collider::cuboid(1.3, 0.6, 0.7),
+        );
+        spawn_affector(
+            app,
+            Transform::from_xyz(-31.5, 0.22, -21.2).with_rotation(Quat::from_rotation_x(0.1)),
+            Collider::cuboid(2.0, 0.1, 2.0),
+        );
+    };
+
+    let mut settings = settings();
+    settings.tile_batch_size = Some(3);
+    let mut batched = app_with_settings(settings);
+    let mut individual = app();
+    spawn_scene(&mut batched);
+    spawn_scene(&mut individual);
+    settle(&mut batched);
+    settle(&mut individual);
+
+    assert_eq!(snapshot(&batched), snapshot(&individual));
+}
+
+#[test]
+fn batched_generation_matches_individual_generation_with_inexact_cell_width() {
+    // Neither the cell width nor the world's corner are exactly representable, rasterizing relative to different origins would round cell boundaries differently.
+    let mut settings = settings();
+    settings.cell_width = 0.3;
+    settings.world_half_extents = 253.7;
+
+    let spawn_scene = |app: &mut App, settings: &NavMeshSettings| {
+        spawn_affector(
+            app,
+            Transform::from_xyz(71.3, 0.0, -48.9),
+            Collider::cuboid(55.0, 0.1, 40.0),
+        );
+        // Posts with sides on cell boundaries throughout the blocks.
+        let corner = settings.get_tile_origin_with_border(UVec2::ZERO);
+        for i in 0..60 {
+            let cell = 1000 + i * 7;
+            let x = corner.x + cell as f32 * settings.cell_width;
+            let z = corner.y + (cell - 200) as f32 * settings.cell_width;
+            spawn_affector(
+                app,
+                Transform::from_xyz(x + 0.45, 0.5, -48.9 + (i as f32 - 30.0) * 1.1),
+                Collider::cuboid(0.45, 0.5, 0.45),
+            );
+            spawn_affector(
+                app,
+                Transform::from_xyz(71.3 + (i as f32 - 30.0) * 1.3, 0.5, z + 0.45),
+                Collider::cuboid(0.45, 0.5, 0.45),
+            );
+        }
+    };
+
+    let mut batched_settings = settings.clone();
+    batched_settings.tile_batch_size = Some(3);
+    let mut batched = app_with_settings(batched_settings);
+    let mut individual = app_with_settings(settings.clone());
+    spawn_scene(&mut batched, &settings);
+    spawn_scene(&mut individual, &settings);
+    settle(&mut batched);
+    settle(&mut individual);
+
+    let (batched_tiles, individual_tiles) = (snapshot(&batched), snapshot(&individual));
+    assert!(batched_tiles.len() > 9);
+    assert_eq!(batched_tiles.len(), individual_tiles.len());
+    for (batched_tile, individual_tile) in batched_tiles.iter().zip(individual_tiles.iter()) {
+        assert_eq!(batched_tile, individual_tile, "tile {}", batched_tile.0);
+    }
+}
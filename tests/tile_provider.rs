@@ -0,0 +1,63 @@
+mod common;
+
+use std::cell::RefCell;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_path_with_provider, find_polygon_path_with_provider, FindPathOptions},
+    tiles::NavMeshTile,
+};
+
+#[test]
+fn path_through_provided_tiles() {
+    let mut settings = settings();
+    settings.walkable_radius = 0;
+
+    // A long floor baked up front, only the tile at -X is resident.
+    let floor = Collider::cuboid(40.0, 0.1, 5.0);
+    let baked = generate_navmesh(
+        &settings,
+        [(&floor, &GlobalTransform::IDENTITY, None, None)],
+    );
+    let resident_floor = Collider::cuboid(12.499, 0.1, 5.0);
+    let resident = generate_navmesh(
+        &settings,
+        [(
+            &resident_floor,
+            &GlobalTransform::from_translation(Vec3::new(-12.5, 0.0, 0.0)),
+            None,
+            None,
+        )],
+    );
+    assert_eq!(resident.get_tiles().len(), 2);
+
+    let requested = RefCell::new(Vec::new());
+    let tile_provider = |tile_coord: UVec2| -> Option<NavMeshTile> {
+        requested.borrow_mut().push(tile_coord);
+        baked.get_tiles().get(&tile_coord).cloned()
+    };
+
+    let (start, end) = (Vec3::new(-15.0, 0.1, 0.0), Vec3::new(35.0, 0.1, 1.0));
+    let options = FindPathOptions::default();
+
+    let polygon_path =
+        find_polygon_path_with_provider(&resident, &settings, start, end, &options, &tile_provider)
+            .unwrap();
+    assert!(polygon_path
+        .iter()
+        .any(|(tile_coord, _)| !resident.get_tiles().contains_key(tile_coord)));
+
+    // Each missing tile is requested once per search.
+    let mut unique = requested.borrow().clone();
+    unique.sort_by_key(|tile_coord| (tile_coord.x, tile_coord.y));
+    unique.dedup();
+    assert_eq!(unique.len(), requested.borrow().len());
+
+    let path = find_path_with_provider(&resident, &settings, start, end, &options, &tile_provider)
+        .unwrap();
+    assert!(path.last().unwrap().distance(end) < 0.5, "{path:?}");
+    assert!(path.first().unwrap().distance(start) < 0.5, "{path:?}");
+}
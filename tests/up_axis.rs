@@ -0,0 +1,195 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_path, find_polygon_path, perform_string_pulling_on_path_in_world_space},
+    tiles::NavMeshTiles,
+    NavMeshSettings,
+};
+
+/// A floor with a box & ramp on it, offset so no edges lie exactly on cell boundaries where rotating could round them into another cell.
+fn scene() -> Vec<(Collider, GlobalTransform)> {
+    vec![
+        (
+            Collider::cuboid(12.0, 0.1, 12.0),
+            Transform::from_xyz(0.013, 0.037, 0.061).into(),
+        ),
+        (
+            Collider::cuboid(1.0, 1.0, 2.0),
+            Transform::from_xyz(2.013, 1.037, -3.061)
+                .with_rotation(Quat::from_rotation_y(0.4))
+                .into(),
+        ),
+        (
+            Collider::cuboid(3.0, 0.1, 1.5),
+            Transform::from_xyz(-4.013, 0.837, 4.061)
+                .with_rotation(Quat::from_rotation_z(0.3))
+                .into(),
+        ),
+    ]
+}
+
+fn generate(settings: &NavMeshSettings, rotation: Quat) -> NavMeshTiles {
+    let scene: Vec<_> = scene()
+        .into_iter()
+        .map(|(collider, transform)| {
+            (
+                collider,
+                GlobalTransform::from_rotation(rotation) * transform,
+            )
+        })
+        .collect();
+
+    generate_navmesh(
+        settings,
+        scene
+            .iter()
+            .map(|(collider, transform)| (collider, transform, None, None)),
+    )
+}
+
+fn assert_paths_match(expected: &[Vec3], actual: &[Vec3]) {
+    assert_eq!(expected.len(), actual.len(), "{expected:?} {actual:?}");
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert!(expected.distance(*actual) < 1e-3, "{expected} {actual}");
+    }
+}
+
+#[test]
+fn z_up_matches_rotated_y_up() {
+    let y_up = settings();
+    let mut z_up = settings();
+    z_up.up = Vec3::Z;
+    let rotation = Quat::from_rotation_arc(Vec3::Y, Vec3::Z);
+
+    let y_tiles = generate(&y_up, Quat::IDENTITY);
+    let z_tiles = generate(&z_up, rotation);
+
+    // Tiles are stored in nav-mesh space, so both nav-meshes are the same.
+    assert!(!y_tiles.get_tiles().is_empty());
+    assert_eq!(y_tiles.get_tiles().len(), z_tiles.get_tiles().len());
+    for (tile_coord, y_tile) in y_tiles.get_tiles() {
+        let z_tile = &z_tiles.get_tiles()[tile_coord];
+        assert_eq!(
+            y_tile.polygons.len(),
+            z_tile.polygons.len(),
+            "tile {tile_coord}"
+        );
+        for (y_vertex, z_vertex) in y_tile.vertices.iter().zip(&z_tile.vertices) {
+            assert!(y_vertex.distance(*z_vertex) < 1e-3, "{y_vertex} {z_vertex}");
+            assert!((rotation * *y_vertex).distance(z_up.to_world_space(*z_vertex)) < 1e-3);
+        }
+    }
+
+    // Goes around the box.
+    let (start, end) = (Vec3::new(2.0, 0.1, -8.0), Vec3::new(2.0, 0.1, 2.0));
+    let y_path = find_path(&y_tiles, &y_up, start, end, None, None).unwrap();
+    let expected: Vec<_> = y_path.iter().map(|point| rotation * *point).collect();
+    assert!(y_path.len() > 2, "{y_path:?}");
+
+    let z_path = find_path(
+        &z_tiles,
+        &z_up,
+        rotation * start,
+        rotation * end,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_paths_match(&expected, &z_path);
+
+    // String pulling a polygon path gives the same world space path.
+    let polygon_path = find_polygon_path(
+        &z_tiles,
+        &z_up,
+        rotation * start,
+        rotation * end,
+        None,
+        None,
+    )
+    .unwrap();
+    let z_path = perform_string_pulling_on_path_in_world_space(
+        &z_tiles,
+        &z_up,
+        rotation * start,
+        rotation * end,
+        &polygon_path,
+    )
+    .unwrap();
+    assert_paths_match(&expected, &z_path);
+}
+
+#[test]
+fn connectivity_graph_is_in_world_space() {
+    let y_up = settings();
+    let mut z_up = settings();
+    z_up.up = Vec3::Z;
+    let rotation = Quat::from_rotation_arc(Vec3::Y, Vec3::Z);
+
+    let y_graph = generate(&y_up, Quat::IDENTITY).build_connectivity_graph(&y_up);
+    let z_graph = generate(&z_up, rotation).build_connectivity_graph(&z_up);
+
+    assert!(!y_graph.nodes.is_empty());
+    assert_eq!(y_graph.nodes.len(), z_graph.nodes.len());
+    for (polygon_ref, y_node) in y_graph.nodes.iter() {
+        let z_node = &z_graph.nodes[polygon_ref];
+        assert!((rotation * y_node.centroid).distance(z_node.centroid) < 1e-3);
+
+        assert_eq!(y_node.edges.len(), z_node.edges.len());
+        for (y_edge, z_edge) in y_node.edges.iter().zip(&z_node.edges) {
+            assert_eq!(y_edge.to, z_edge.to);
+            assert!((rotation * y_edge.portal.0).distance(z_edge.portal.0) < 1e-3);
+            assert!((rotation * y_edge.portal.1).distance(z_edge.portal.1) < 1e-3);
+            assert!((y_edge.cost - z_edge.cost).abs() < 1e-3);
+        }
+    }
+}
+
+fn obj_vertices(obj: &str) -> Vec<Vec3> {
+    obj.lines()
+        .filter_map(|line| line.strip_prefix("v "))
+        .map(|line| {
+            let coordinates: Vec<f32> = line
+                .split(' ')
+                .map(|coordinate| coordinate.parse().unwrap())
+                .collect();
+            Vec3::from_slice(&coordinates)
+        })
+        .collect()
+}
+
+#[test]
+fn export_obj_is_in_world_space() {
+    let y_up = settings();
+    let mut z_up = settings();
+    z_up.up = Vec3::Z;
+    let rotation = Quat::from_rotation_arc(Vec3::Y, Vec3::Z);
+
+    let y_vertices = obj_vertices(&generate(&y_up, Quat::IDENTITY).export_obj(&y_up));
+    let z_vertices = obj_vertices(&generate(&z_up, rotation).export_obj(&z_up));
+
+    assert!(!y_vertices.is_empty());
+    let expected: Vec<_> = y_vertices.iter().map(|vertex| rotation * *vertex).collect();
+    assert_paths_match(&expected, &z_vertices);
+}
+
+#[test]
+fn invalid_up_falls_back_to_y() {
+    let y_tiles = generate(&settings(), Quat::IDENTITY);
+
+    for up in [Vec3::ZERO, Vec3::NAN, Vec3::new(f32::INFINITY, 1.0, 0.0)] {
+        let mut settings = settings();
+        settings.up = up;
+        assert_eq!(settings.get_up_rotation(), Quat::IDENTITY, "{up}");
+
+        let tiles = generate(&settings, Quat::IDENTITY);
+        assert_eq!(
+            tiles.export_obj(&settings),
+            y_tiles.export_obj(&settings),
+            "{up}"
+        );
+    }
+}
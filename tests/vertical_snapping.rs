@@ -0,0 +1,95 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh,
+    query::{find_polygon_path, find_polygon_path_with_options, FindPathOptions},
+    tiles::NavMeshTiles,
+};
+
+/// A floor at y = 0.1 with a bridge at y = 3.1 spanning the middle of it.
+fn stacked_floors() -> NavMeshTiles {
+    let floor = Collider::cuboid(10.0, 0.1, 10.0);
+    let bridge = Collider::cuboid(10.0, 0.1, 2.0);
+
+    generate_navmesh(
+        &settings(),
+        [
+            (&floor, &GlobalTransform::IDENTITY, None, None),
+            (
+                &bridge,
+                &GlobalTransform::from_translation(Vec3::new(0.0, 3.0, 0.0)),
+                None,
+                None,
+            ),
+        ],
+    )
+}
+
+#[test]
+fn position_under_bridge_snaps_to_floor() {
+    let settings = settings();
+    let tiles = stacked_floors();
+
+    // Standing on the floor directly under the bridge.
+    let position = Vec3::new(2.0, 0.3, 0.5);
+    let (_, _, point) = tiles
+        .find_closest_polygon_in_box(&settings, position, 5.0)
+        .unwrap();
+    assert!(point.y < 1.0, "{point}");
+
+    let end = Vec3::new(-8.0, 0.1, 8.0);
+    let path = find_polygon_path(&tiles, &settings, position, end, None, None).unwrap();
+    let start_polygon = tiles.get_polygon(path[0]).unwrap();
+    assert!(start_polygon.centroid().y < 1.0);
+}
+
+#[test]
+fn vertical_snap_weight_penalizes_height_difference() {
+    let settings = settings();
+    // A lower floor at -X & a higher one at +X, 3 units above it.
+    let floor = Collider::cuboid(5.0, 0.1, 5.0);
+    let tiles = generate_navmesh(
+        &settings,
+        [
+            (
+                &floor,
+                &GlobalTransform::from_translation(Vec3::new(-5.0, 0.0, 0.0)),
+                None,
+                None,
+            ),
+            (
+                &floor,
+                &GlobalTransform::from_translation(Vec3::new(5.0, 3.0, 0.0)),
+                None,
+                None,
+            ),
+        ],
+    );
+
+    // Below the higher floor, closer to it than to the lower floor's edge.
+    let position = Vec3::new(3.0, 0.5, 0.0);
+    let (_, _, unweighted) = tiles
+        .find_closest_polygon_in_box(&settings, position, 5.0)
+        .unwrap();
+    let (_, _, weighted) = tiles
+        .find_closest_polygon_in_box_weighted(&settings, position, 5.0, 2.0)
+        .unwrap();
+    assert!(unweighted.y > 2.0, "{unweighted}");
+    assert!(weighted.y < 1.0, "{weighted}");
+
+    let path = find_polygon_path_with_options(
+        &tiles,
+        &settings,
+        position,
+        Vec3::new(-5.0, 0.1, 0.0),
+        &FindPathOptions {
+            vertical_snap_weight: Some(2.0),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(tiles.get_polygon(path[0]).unwrap().centroid().y < 1.0);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use common::*;
+use oxidized_navigation::{
+    generate_navmesh, query::find_path, tiles::NavMeshTiles, NavMeshSettings,
+};
+
+/// A 26x14 plane spanning several tiles, not aligned with tile borders.
+fn plane(settings: &NavMeshSettings) -> NavMeshTiles {
+    let floor = Collider::cuboid(13.0, 0.1, 7.0);
+    let transform = GlobalTransform::from_translation(Vec3::new(1.3, 0.0, -2.1));
+
+    generate_navmesh(settings, [(&floor, &transform, None, None)])
+}
+
+#[test]
+fn zero_radius_covers_full_extent() {
+    let mut settings = settings();
+    settings.walkable_radius = 0;
+    let tiles = plane(&settings);
+
+    let (min, max) = polygon_extents(&tiles);
+    assert!(min.x <= -11.7 && min.z <= -9.1, "{min}");
+    assert!(max.x >= 14.3 && max.z >= 4.9, "{max}");
+
+    let surface_area: f32 = tiles
+        .get_tiles()
+        .values()
+        .flat_map(|tile| tile.polygons.iter())
+        .map(|polygon| polygon.surface_area())
+        .sum();
+    assert!(surface_area >= 26.0 * 14.0, "{surface_area}");
+
+    // Tiles are still linked without a border.
+    let end = Vec3::new(13.0, 0.1, 4.0);
+    let path = find_path(
+        &tiles,
+        &settings,
+        Vec3::new(-10.0, 0.1, -8.0),
+        end,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(path.last().unwrap().distance(end) < 0.5, "{path:?}");
+
+    // Eroding pulls the edges in.
+    settings.walkable_radius = 1;
+    let (eroded_min, eroded_max) = polygon_extents(&plane(&settings));
+    assert!(min.x < eroded_min.x && min.z < eroded_min.z);
+    assert!(max.x > eroded_max.x && max.z > eroded_max.z);
+}